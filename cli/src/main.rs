@@ -0,0 +1,735 @@
+use std::{
+    env,
+    fs,
+    process::ExitCode,
+    io::{self, Read, Write},
+};
+use strim_core::{
+    base64::Alphabet,
+    invalid::Invalid,
+    lint::{Lint, NAMES},
+    transform::{
+        Options,
+        Literal,
+        Lang,
+        Decode,
+        Delimiter,
+        Encode,
+        Output,
+        Error,
+    },
+};
+
+const USAGE: &str = "\
+Usage: strim [OPTIONS] [FILE]
+
+Trims the content of FILE (or of the standard input, if FILE is not given or
+it is `-`) the same way as `trim!` trims a literal, and writes the result to
+the standard output as it is, i.e. without a trailing line break.  The flags
+can be given a value as well, e.g. `--debug=false`.
+
+Options:
+    --literal <KIND>      Process the input as a `string` (the default),
+                          a `byte-string`, or a `c-string` literal
+    --join-with <DELIM>   Join the lines together with DELIM, which can be
+                          `MAIN_SEPARATOR` or contain the escapes `\\n`,
+                          `\\r`, `\\t`, `\\0`, `\\\\`, `\\xHH`, and `\\u{...}`
+    --lang <LANG>         Minify the input as `graphql` or `regex`
+    --decode <DECODING>   Decode the input as `hex`, `base64`, `base64_url`,
+                          or `pem:LABEL`
+    --encode <ENCODING>   Encode the output as `utf16`, `utf16_nul`, or
+                          `utf32`, whose code units are written in
+                          little-endian byte order
+    --as <KIND>           Check the output as `str`, `c_str`, `bytes`,
+                          `os_str`, or `path`
+    --max-len <N>         Require the output to be at most N bytes long
+    --min-lines <N>       Require the input to have at least N non-blank
+                          lines
+    --ascii-only          Require the output to be ASCII
+    --forbid <CHARS>      Require the output not to contain any of CHARS,
+                          which can contain the same escapes as DELIM
+    --require-utf8        Require the output to be valid UTF-8
+    --var <NAME=VALUE>    Substitute the `{NAME}` placeholders with VALUE,
+                          which can contain the same escapes as DELIM
+                          (can be repeated)
+    --cfg-lines           Keep the lines marked with `@cfg(PREDICATE)` only
+                          if their predicates hold
+    --cfg <PREDICATE>     Consider PREDICATE to hold, which implies
+                          `--cfg-lines` (can be repeated)
+    --allow <LINT>        Do not warn about LINT (can be repeated)
+    --allow-invisible     Allow invisible and bidirectional characters
+    --debug               Write a preview of the trimming to standard error
+    -h, --help            Print this help and exit
+    -V, --version         Print the version and exit";
+
+/// The options of the transformations, and the ones of the tool itself.
+struct Arguments {
+    options: Options,
+    allowed: Vec<Lint>,
+    debug: bool,
+    file: Option<String>,
+}
+
+enum Command {
+    Help,
+    Version,
+    Trim(Box<Arguments>),
+}
+
+/// Unescapes `value` the same way the content of a string literal would be.
+fn unescaped(value: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut characters = value.chars();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend(character.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+
+        match characters.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('t') => bytes.push(b'\t'),
+            Some('0') => bytes.push(b'\0'),
+            Some(quoted @ ('\\' | '\'' | '"')) => bytes.push(quoted as u8),
+            Some('x') => {
+                let digits: String = characters.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| digits.len() == 2)
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid escape `\\x{digits}`, expected `\\xHH`",
+                        )
+                    })?;
+                bytes.push(byte);
+            },
+            Some('u') => {
+                let rest = characters.as_str();
+                let character = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .and_then(|(digits, _)| {
+                        u32::from_str_radix(&digits.replace('_', ""), 16).ok()
+                    })
+                    .and_then(char::from_u32)
+                    .ok_or("Invalid escape `\\u`, expected `\\u{...}`")?;
+                let mut buffer = [0; 4];
+                bytes.extend(character.encode_utf8(&mut buffer).as_bytes());
+
+                let end = rest.find('}').map_or(rest.len(), |end| end + 1);
+                characters = rest[end..].chars();
+            },
+            Some(other) => return Err(format!("Unknown escape `\\{other}`")),
+            None => return Err(String::from("Incomplete escape `\\`")),
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn parse_literal(value: &str) -> Result<Literal, String> {
+    match value {
+        "string" => Ok(Literal::String),
+        "byte-string" => Ok(Literal::ByteString),
+        "c-string" => Ok(Literal::CString),
+        _ => Err(format!(
+            "Expected either `string`, `byte-string`, or `c-string` as a \
+             literal, but got `{value}`",
+        )),
+    }
+}
+
+fn parse_delimiter(value: &str) -> Result<Delimiter, String> {
+    match value {
+        "MAIN_SEPARATOR" => Ok(Delimiter::MainSeparator),
+        _ => unescaped(value).map(Delimiter::Bytes),
+    }
+}
+
+fn parse_lang(value: &str) -> Result<Lang, String> {
+    match value {
+        "graphql" => Ok(Lang::GraphQl),
+        "regex" => Ok(Lang::Regex),
+        _ => Err(format!(
+            "Expected either `graphql` or `regex` as a language, but got \
+             `{value}`",
+        )),
+    }
+}
+
+fn parse_decode(value: &str) -> Result<Decode, String> {
+    match value.split_once(':') {
+        None if value == "hex" => Ok(Decode::Hex),
+        None if value == "base64" => Ok(Decode::Base64(Alphabet::Standard)),
+        None if value == "base64_url" => {
+            Ok(Decode::Base64(Alphabet::UrlSafe))
+        },
+        Some(("pem", label)) if !label.is_empty() => {
+            Ok(Decode::Pem(label.to_string()))
+        },
+        _ => Err(format!(
+            "Expected either `hex`, `base64`, `base64_url`, or `pem:LABEL` as \
+             a decoding, but got `{value}`",
+        )),
+    }
+}
+
+fn parse_encode(value: &str) -> Result<Encode, String> {
+    match value {
+        "utf16" => Ok(Encode::Utf16),
+        "utf16_nul" => Ok(Encode::Utf16Nul),
+        "utf32" => Ok(Encode::Utf32),
+        _ => Err(format!(
+            "Expected either `utf16`, `utf16_nul`, or `utf32` as an encoding, \
+             but got `{value}`",
+        )),
+    }
+}
+
+fn parse_output(value: &str) -> Result<Output, String> {
+    match value {
+        "str" => Ok(Output::String),
+        "c_str" => Ok(Output::CString),
+        "bytes" => Ok(Output::ByteString),
+        "os_str" => Ok(Output::OsString),
+        "path" => Ok(Output::Path),
+        _ => Err(format!(
+            "Expected either `str`, `c_str`, `bytes`, `os_str`, or `path` as \
+             a kind of output, but got `{value}`",
+        )),
+    }
+}
+
+fn parse_limit(name: &str, value: &str) -> Result<usize, String> {
+    value.parse().map_err(|_| {
+        format!(
+            "Expected a non-negative integer to follow `{name}`, but got \
+             `{value}`",
+        )
+    })
+}
+
+fn parse_forbid(value: &str) -> Result<Vec<char>, String> {
+    let characters = String::from_utf8(unescaped(value)?)
+        .map_err(|_| "Forbidden characters must be valid UTF-8")?;
+    if characters.is_empty() {
+        return Err(String::from("Expected the forbidden characters"));
+    }
+
+    Ok(characters.chars().collect())
+}
+
+/// Parses `NAME=VALUE` of a variable, whose value is unescaped.
+fn parse_var(value: &str) -> Result<(String, String), String> {
+    let Some((name, value)) = value.split_once('=') else {
+        return Err(format!(
+            "Expected a variable as `NAME=VALUE`, but got `{value}`",
+        ));
+    };
+    let is_name = name
+        .chars()
+        .enumerate()
+        .all(|(index, character)| match index {
+            0 => character.is_alphabetic() || character == '_',
+            _ => character.is_alphanumeric() || character == '_',
+        });
+    if name.is_empty() || !is_name {
+        return Err(format!(
+            "Expected the name of a variable, but got `{name}`",
+        ));
+    }
+
+    let value = String::from_utf8(unescaped(value)?).map_err(|_| {
+        format!("Value of variable `{name}` must be valid UTF-8")
+    })?;
+
+    Ok((name.to_string(), value))
+}
+
+fn parse_lint(value: &str) -> Result<Lint, String> {
+    Lint::named(value).ok_or_else(|| {
+        let names: Vec<_> =
+            NAMES.iter().map(|name| format!("`{name}`")).collect();
+        let (last, names) = names.split_last().expect("No lints");
+        format!(
+            "Expected either {}, or {last} as a lint, but got `{value}`",
+            names.join(", "),
+        )
+    })
+}
+
+/// Parses the value of the flag called `name`, which is `true` if it is not
+/// given.
+fn parse_flag(name: &str, value: Option<&str>) -> Result<bool, String> {
+    match value {
+        None | Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(value) => Err(format!(
+            "Expected either `true` or `false` as the value of `{name}`, but \
+             got `{value}`",
+        )),
+    }
+}
+
+/// Describes the `error` of the transformations of `input`, where the options
+/// are referred to by their names on the command line.
+fn described(error: Error, input: &[u8]) -> String {
+    error.describe(input, |name| match name {
+        "vars" => String::from("`--var`"),
+        name => format!("`--{}`", name.replace('_', "-")),
+    })
+}
+
+/// Parses the command-line `arguments` (without the name of the program).
+fn parse(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Command, String> {
+    let mut parsed = Arguments {
+        options: Options::default(),
+        allowed: Vec::new(),
+        debug: false,
+        file: None,
+    };
+    let Arguments { options, allowed, debug, file } = &mut parsed;
+    let mut arguments = arguments.into_iter();
+    let mut only_files = false;
+    let mut cfg_lines = false;
+    let mut holding = Vec::new();
+
+    while let Some(argument) = arguments.next() {
+        let is_option =
+            !only_files && argument.starts_with('-') && argument != "-";
+        if !is_option {
+            if let Some(file) = file {
+                return Err(format!(
+                    "Expected at most one file, but got both `{file}` and \
+                     `{argument}`",
+                ));
+            }
+            *file = Some(argument);
+            continue;
+        }
+
+        let (name, inline) = match argument.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (argument, None),
+        };
+        let mut value = || {
+            inline.clone().or_else(|| arguments.next()).ok_or_else(|| {
+                format!("Expected a value to follow `{name}`")
+            })
+        };
+        let flag = || parse_flag(&name, inline.as_deref());
+        let none = || match &inline {
+            Some(_) => Err(format!("Unexpected value for `{name}`")),
+            None => Ok(()),
+        };
+
+        match name.as_str() {
+            "--" => {
+                none()?;
+                only_files = true;
+            },
+            "-h" | "--help" => return none().map(|()| Command::Help),
+            "-V" | "--version" => return none().map(|()| Command::Version),
+            "--literal" => options.literal = parse_literal(&value()?)?,
+            "--join-with" => {
+                options.join_with = Some(parse_delimiter(&value()?)?);
+            },
+            "--lang" => options.lang = Some(parse_lang(&value()?)?),
+            "--decode" => options.decode = Some(parse_decode(&value()?)?),
+            "--encode" => options.encode = Some(parse_encode(&value()?)?),
+            "--as" => options.output = Some(parse_output(&value()?)?),
+            "--max-len" => {
+                options.constraints.max_len =
+                    Some(parse_limit(&name, &value()?)?);
+            },
+            "--min-lines" => {
+                options.constraints.min_lines =
+                    Some(parse_limit(&name, &value()?)?);
+            },
+            "--ascii-only" => options.constraints.ascii_only = flag()?,
+            "--forbid" => {
+                options.constraints.forbid = parse_forbid(&value()?)?;
+            },
+            "--require-utf8" => options.constraints.require_utf8 = flag()?,
+            "--var" => {
+                let (variable, value) = parse_var(&value()?)?;
+                let vars = options.vars.get_or_insert_with(Vec::new);
+                if vars.iter().any(|(other, _)| *other == variable) {
+                    return Err(format!("Variable `{variable}` is already set"));
+                }
+                vars.push((variable, value));
+            },
+            "--cfg-lines" => cfg_lines = flag()?,
+            "--cfg" => holding.push(value()?),
+            "--allow" => allowed.push(parse_lint(&value()?)?),
+            "--allow-invisible" => options.allow_invisible = flag()?,
+            "--debug" => *debug = flag()?,
+            _ => return Err(format!("Unknown option `{name}`")),
+        }
+    }
+
+    if cfg_lines || !holding.is_empty() {
+        options.cfg_lines = Some(holding);
+    }
+    parsed.options.check().map_err(|error| described(error, &[]))?;
+
+    Ok(Command::Trim(Box::new(parsed)))
+}
+
+/// Applies the transformations of `arguments` to `input`, and returns the
+/// output together with the messages to be written to the standard error,
+/// i.e. the warnings, and the preview (if it is requested).
+fn trimmed(
+    arguments: &Arguments,
+    input: &[u8],
+) -> Result<(Vec<u8>, Vec<String>), String> {
+    let options = &arguments.options;
+    let selected = options
+        .selected(input)
+        .map_err(|error| described(error, input))?;
+    let output = options
+        .transformed(&selected)
+        .map_err(|error| described(error, &selected))?;
+
+    let mut messages: Vec<_> = options
+        .lints(&selected, &output)
+        .into_iter()
+        .filter(|(lint, _)| !arguments.allowed.contains(lint))
+        .map(|(lint, range)| {
+            let message = match range {
+                Some(range) => {
+                    Invalid::new(range, lint.message()).located(&selected)
+                },
+                None => lint.message().to_string(),
+            };
+            format!(
+                "strim: warning: {message} (silence with `--allow {}`)",
+                lint.name(),
+            )
+        })
+        .collect();
+    if arguments.debug {
+        messages.push(options.preview(&selected, &output));
+    }
+
+    Ok((options.encoded(output), messages))
+}
+
+fn read(file: Option<&str>) -> io::Result<Vec<u8>> {
+    match file {
+        Some(file) if file != "-" => fs::read(file),
+        _ => {
+            let mut input = Vec::new();
+            io::stdin().read_to_end(&mut input)?;
+            Ok(input)
+        },
+    }
+}
+
+fn main() -> ExitCode {
+    let arguments = match parse(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Ok(Command::Version) => {
+            println!("strim {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        },
+        Ok(Command::Trim(arguments)) => arguments,
+        Err(message) => {
+            eprintln!("strim: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+
+    let input = match read(arguments.file.as_deref()) {
+        Ok(input) => input,
+        Err(error) => {
+            let file = arguments.file.as_deref().unwrap_or("-");
+            eprintln!("strim: Cannot read `{file}`: {error}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    match trimmed(&arguments, &input) {
+        Ok((output, messages)) => {
+            for message in messages {
+                eprintln!("{message}");
+            }
+
+            let mut stdout = io::stdout().lock();
+            match stdout.write_all(&output).and_then(|_| stdout.flush()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(error) => {
+                    eprintln!("strim: Cannot write the output: {error}");
+                    ExitCode::FAILURE
+                },
+            }
+        },
+        Err(message) => {
+            eprintln!("strim: {message}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strim::trim;
+    use super::*;
+
+    fn parsed(arguments: &[&str]) -> Result<Arguments, String> {
+        let arguments = arguments.iter().map(|argument| argument.to_string());
+        match parse(arguments)? {
+            Command::Trim(arguments) => Ok(*arguments),
+            _ => panic!("Expected options to trim with"),
+        }
+    }
+
+    fn messages(arguments: &[&str], input: &[u8]) -> Vec<String> {
+        trimmed(&parsed(arguments).unwrap(), input).unwrap().1
+    }
+
+    fn run(arguments: &[&str], input: &[u8]) -> Result<Vec<u8>, String> {
+        trimmed(&parsed(arguments).unwrap(), input).map(|(output, _)| output)
+    }
+
+    fn units<const N: usize>(units: &[u16; N]) -> Vec<u8> {
+        units.iter().flat_map(|unit| unit.to_le_bytes()).collect()
+    }
+
+    const INPUT: &str = "
+        Hello,
+            W\u{f6}rld!\t
+
+        \u{3000}Bye.
+    ";
+
+    #[test]
+    fn same_as_macro() {
+        assert_eq!(
+            run(&[], INPUT.as_bytes()).unwrap(),
+            trim!("
+                Hello,
+                    W\u{f6}rld!\t
+
+                \u{3000}Bye.
+            ").as_bytes(),
+        );
+        assert_eq!(
+            run(&["--join-with", "\\n"], INPUT.as_bytes()).unwrap(),
+            trim!("
+                Hello,
+                    W\u{f6}rld!\t
+
+                \u{3000}Bye.
+            ", join_with = '\n').as_bytes(),
+        );
+        assert_eq!(
+            run(&["--literal=byte-string", "--join-with= "], b"a \r\n\n b\r")
+                .unwrap(),
+            trim!(
+                b"a \r\n\n b\r",
+                join_with = b' ',
+                allow(lone_carriage_return),
+            ),
+        );
+        assert_eq!(
+            run(&["--literal", "c-string", "--join-with", ","], b" a \n b ")
+                .unwrap(),
+            trim!(c" a \n b ", join_with = c",").to_bytes(),
+        );
+        assert_eq!(
+            run(&["--join-with", "MAIN_SEPARATOR"], b"usr\n  bin").unwrap(),
+            trim!("usr\n  bin", join_with = MAIN_SEPARATOR).as_bytes(),
+        );
+    }
+
+    #[test]
+    fn same_as_macro_with_parameters() {
+        assert_eq!(
+            run(&["--encode", "utf16"], " a\n  \u{e9}".as_bytes()).unwrap(),
+            units(trim!(" a\n  \u{e9}", encode = utf16)),
+        );
+        assert_eq!(
+            run(&["--encode=utf16_nul", "--join-with= "], b"a\n b").unwrap(),
+            units(trim!("a\n b", encode = utf16_nul, join_with = ' ')),
+        );
+        assert_eq!(
+            run(&["--encode", "utf32"], "\u{1f600}".as_bytes()).unwrap(),
+            0x1f600_u32.to_le_bytes(),
+        );
+        assert_eq!(
+            run(&["--literal", "byte-string", "--as", "str"], b" a\n b ")
+                .unwrap(),
+            trim!(b" a\n b ", as = str).as_bytes(),
+        );
+        assert_eq!(
+            run(&["--var", "name=W\\u{f6}rld"], b"Hello,\n {name}!").unwrap(),
+            trim!(
+                "Hello,\n {name}!",
+                join_with = "",
+                vars = { name = "W\u{f6}rld" },
+                allow(needless_join_with),
+            )
+            .as_bytes(),
+        );
+        assert_eq!(
+            run(&["--cfg", "test", "--join-with", " "], b"a\n@cfg(test) b\n")
+                .unwrap(),
+            trim!("a\n@cfg(test) b\n", join_with = ' ', cfg_lines).as_bytes(),
+        );
+        assert_eq!(
+            run(&["--cfg-lines", "--join-with", " "], b"a\n@cfg(test) b\n")
+                .unwrap(),
+            b"a",
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        assert_eq!(
+            run(&["--max-len", "3", "--ascii-only"], "a\u{e9}\nb".as_bytes())
+                .unwrap_err(),
+            "The trimmed literal is 4 bytes long, which exceeds `max_len = \
+             3`\nThe trimmed literal contains the non-ASCII character `\u{e9}` \
+             at byte 1",
+        );
+        assert!(run(&["--min-lines=2"], b"a\n\n").is_err());
+        assert!(run(&["--min-lines=2"], b"a\nb").is_ok());
+        assert!(run(&["--forbid", "\\t"], b"a\tb\n").is_err());
+        assert!(run(&["--literal=byte-string", "--require-utf8"], b"\xff")
+            .is_err());
+        assert!(run(&["--encode", "utf16_nul"], b"a\0").is_err());
+        assert_eq!(
+            run(&["--var", "a=1"], b"{a} {b}").unwrap_err(),
+            "Unknown placeholder `{b}` in the trimmed string",
+        );
+    }
+
+    #[test]
+    fn flags() {
+        assert!(parsed(&["--debug=false"]).is_ok_and(|arguments| {
+            !arguments.debug
+        }));
+        assert!(parsed(&["--debug=true"]).is_ok_and(|arguments| {
+            arguments.debug
+        }));
+        assert!(run(&["--allow-invisible=false"], "\u{200b}".as_bytes())
+            .is_err());
+        assert!(run(&["--allow-invisible=true"], "\u{200b}".as_bytes())
+            .is_ok());
+        assert!(parsed(&["--cfg-lines=false"]).is_ok_and(|arguments| {
+            arguments.options.cfg_lines.is_none()
+        }));
+        assert_eq!(
+            parsed(&["--debug=yes"]).err().unwrap(),
+            "Expected either `true` or `false` as the value of `--debug`, but \
+             got `yes`",
+        );
+        assert_eq!(
+            parsed(&["--help=true"]).err().unwrap(),
+            "Unexpected value for `--help`",
+        );
+    }
+
+    #[test]
+    fn warnings() {
+        assert_eq!(
+            messages(&["--join-with", " "], b"a"),
+            ["strim: warning: `join_with` has no effect, as there is at most \
+              one non-blank line in the literal (silence with \
+              `--allow needless_join_with`)"],
+        );
+        assert!(messages(
+            &["--join-with", " ", "--allow", "needless_join_with"],
+            b"a",
+        )
+        .is_empty());
+        assert_eq!(messages(&["--debug"], b"a").len(), 1);
+        assert!(parsed(&["--allow", "bogus"]).is_err());
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(
+            run(&["--lang", "graphql"], b"{ a, b # c\n }").unwrap(),
+            trim!("{ a, b # c\n }", lang = graphql).as_bytes(),
+        );
+        assert_eq!(
+            run(&["--decode", "hex"], b"  48 65\n  6c 6c 6f\n").unwrap(),
+            trim!("  48 65\n  6c 6c 6f\n", decode = hex),
+        );
+        assert_eq!(
+            run(&["--decode", "base64"], b"\n  SGVs\n  bG8=\n").unwrap(),
+            b"Hello",
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            run(&[], "a\n b\u{200b}".as_bytes()).unwrap_err(),
+            "Invisible character `U+200B` (zero width space) (at line 2, \
+             column 3)",
+        );
+        assert!(run(&["--allow-invisible"], "\u{200b}".as_bytes()).is_ok());
+        assert!(run(&[], b"\xff").is_err());
+        assert!(run(&["--literal", "c-string"], b"a\0").is_err());
+        assert!(run(&["--decode", "hex"], b"4").is_err());
+    }
+
+    #[test]
+    fn invalid_options() {
+        let parsed = |arguments: &[&str]| parsed(arguments).err();
+
+        assert_eq!(
+            parsed(&["--lang", "graphql", "--join-with", " "]).unwrap(),
+            "`--join-with` cannot be combined with `--lang`",
+        );
+        assert_eq!(
+            parsed(&["--encode", "utf16", "--as", "str"]).unwrap(),
+            "`--as` cannot be combined with `--encode`",
+        );
+        assert_eq!(
+            parsed(&["--literal", "c-string", "--var", "a=b"]).unwrap(),
+            "`--var` can only be used with string literals",
+        );
+        assert_eq!(
+            parsed(&["--join-with", "\u{200b}"]).unwrap(),
+            "Invalid delimiter: Invisible character `U+200B` (zero width \
+             space)",
+        );
+        assert_eq!(
+            parsed(&["--literal", "c-string", "--join-with", "\\0"]).unwrap(),
+            "Delimiter for C-string cannot be the nul-terminator",
+        );
+        assert_eq!(parsed(&["--bogus"]).unwrap(), "Unknown option `--bogus`");
+        assert_eq!(
+            parsed(&["--join-with"]).unwrap(),
+            "Expected a value to follow `--join-with`",
+        );
+        assert!(parsed(&["a", "b"]).is_some());
+        assert!(parsed(&["--", "-", "--"]).is_some());
+        assert!(parsed(&["--", "-a"]).is_none());
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            unescaped(r"a\n\t\\\x41\u{f6}b").unwrap(),
+            "a\n\t\\A\u{f6}b".as_bytes(),
+        );
+        assert_eq!(unescaped(r"\xff").unwrap(), b"\xff");
+        assert!(unescaped(r"\x4").is_err());
+        assert!(unescaped(r"\u{110000}").is_err());
+        assert!(unescaped(r"\q").is_err());
+        assert!(unescaped("\\").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use super::{bytes::Bytes as _, invalid::Invalid};
+
+const PADDING: u8 = b'=';
+
+#[derive(Clone, Copy)]
+pub enum Alphabet {
+    Standard,
+    UrlSafe,
+}
+
+impl Alphabet {
+    fn sextet_of(self, byte: u8) -> Option<u8> {
+        match (self, byte) {
+            (_, b'A'..=b'Z') => Some(byte - b'A'),
+            (_, b'a'..=b'z') => Some(byte - b'a' + 26),
+            (_, b'0'..=b'9') => Some(byte - b'0' + 52),
+            (Self::Standard, b'+') | (Self::UrlSafe, b'-') => Some(62),
+            (Self::Standard, b'/') | (Self::UrlSafe, b'_') => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes base64 encoded, already trimmed `lines` into bytes.  Any whitespace
+/// within the lines is ignored, and the padding at the end is optional.
+/// Errors refer to the whole line in which the problem was found.
+pub fn decode<'a>(
+    value: &'a [u8],
+    lines: impl Iterator<Item = &'a [u8]>,
+    alphabet: Alphabet,
+) -> Result<Vec<u8>, Invalid> {
+    let mut decoded = Vec::with_capacity(value.len() / 4 * 3);
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    let mut sextets = 0_usize;
+    let mut padding = 0_usize;
+    let mut last_line = 0..0;
+
+    for line in lines {
+        let offset = value.offset_of(line);
+        last_line = offset..offset + line.len();
+
+        for (i, &byte) in line.iter().enumerate() {
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+
+            if byte == PADDING {
+                padding += 1;
+                if padding > 2 {
+                    let message = "Too much padding in base64 data";
+                    return Err(Invalid::new(last_line, message));
+                }
+
+                continue;
+            }
+
+            let sextet = alphabet.sextet_of(byte).filter(|_| padding == 0);
+            let Some(sextet) = sextet else {
+                let Invalid { message, .. } =
+                    Invalid::unexpected(value, offset + i, "base64 character");
+                let message = if padding == 0 {
+                    message
+                } else {
+                    format!("{message} after padding")
+                };
+                return Err(Invalid::new(last_line, message));
+            };
+
+            buffer = (buffer << 6) | u32::from(sextet);
+            bits += 6;
+            sextets += 1;
+
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+    }
+
+    let is_truncated = sextets % 4 == 1;
+    let is_mispadded = padding > 0 && (sextets + padding) % 4 != 0;
+    if is_truncated || is_mispadded {
+        let message = "Invalid length of base64 data";
+        return Err(Invalid::new(last_line, message));
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte_string::Lines;
+
+    fn decoded(
+        value: &[u8],
+        alphabet: Alphabet,
+    ) -> Result<Vec<u8>, Invalid> {
+        decode(value, Lines::from(value), alphabet)
+    }
+
+    #[test]
+    fn padded_and_unpadded() {
+        let decode = |value| decoded(value, Alphabet::Standard).ok().unwrap();
+
+        assert_eq!(decode(b""), b"");
+        assert_eq!(decode(b"TWFu"), b"Man");
+        assert_eq!(decode(b"TWE="), b"Ma");
+        assert_eq!(decode(b"TWE"), b"Ma");
+        assert_eq!(decode(b"TQ=="), b"M");
+        assert_eq!(decode(b"TQ"), b"M");
+        assert_eq!(decode(b"\n  SGVs\n  bG8s IFdv\n  cmxkIQ==\n"),
+                   b"Hello, World!");
+    }
+
+    #[test]
+    fn alphabets() {
+        assert_eq!(decoded(b"-_-_", Alphabet::UrlSafe).ok().unwrap(),
+                   b"\xfb\xff\xbf");
+        assert_eq!(decoded(b"+/+/", Alphabet::Standard).ok().unwrap(),
+                   b"\xfb\xff\xbf");
+        assert!(decoded(b"+/+/", Alphabet::UrlSafe).is_err());
+        assert!(decoded(b"-_-_", Alphabet::Standard).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let decode = |value| decoded(value, Alphabet::Standard).err().unwrap();
+
+        assert_eq!(decode(b"TWFu\n  TW!u").range, 7..11);
+        assert_eq!(decode(b"TWFu\nT").range, 5..6);
+        assert_eq!(decode(b"TQ=\n").range, 0..3);
+        assert_eq!(decode(b"TQ===").range, 0..5);
+        assert_eq!(decode(b"TQ==\nTWFu").range, 5..9);
+    }
+}
@@ -14,7 +14,7 @@ impl<'a> Lines<'a> {
     fn new(slice: &'a [u8]) -> Self {
         Self {
             slice,
-            bytes: slice.into_iter().enumerate(),
+            bytes: slice.iter().enumerate(),
             index: 0,
         }
     }
@@ -34,7 +34,7 @@ impl<'a> Iterator for Lines<'a> {
         let mut previous_i = 0;
         let mut previous_byte = NULL;
 
-        while let Some((current_i, &current_byte)) = self.bytes.next() {
+        for (current_i, &current_byte) in self.bytes.by_ref() {
             next_i = current_i + 1;
 
             if current_byte == LINE_FEED {
@@ -61,17 +61,23 @@ impl<'a> Iterator for Lines<'a> {
 }
 
 pub trait Bytes {
-    fn lines(&self) -> Lines;
+    fn lines(&self) -> Lines<'_>;
 
     fn trim(&self) -> &[u8];
+
+    /// Returns the offset of `part` relative to the start of `self`, where
+    /// `part` must be a sub-slice of `self` (e.g. a line yielded by [`Lines`]).
+    fn offset_of(&self, part: &[u8]) -> usize;
 }
 
 impl Bytes for [u8] {
-    fn lines(&self) -> Lines {
+    fn lines(&self) -> Lines<'_> {
         Lines::new(self)
     }
 
+    // NOTE: `clippy` is not aware of the `rustversion` gating
     #[rustversion::since(1.80.0)]
+    #[allow(clippy::incompatible_msrv)]
     fn trim(&self) -> &[u8] {
         self.trim_ascii()
     }
@@ -101,6 +107,13 @@ impl Bytes for [u8] {
 
         &trimmed[..end]
     }
+
+    fn offset_of(&self, part: &[u8]) -> usize {
+        let offset = (part.as_ptr() as usize) - (self.as_ptr() as usize);
+        debug_assert!(offset + part.len() <= self.len());
+
+        offset
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+use super::bytes::{self, Bytes as _};
+
+pub struct Lines<'a> {
+    lines: bytes::Lines<'a>,
+}
+
+impl<'a> From<&'a [u8]> for Lines<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { lines: bytes.lines() }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed)
+            }
+        }
+    }
+}
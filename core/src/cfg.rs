@@ -0,0 +1,258 @@
+use std::ops::Range;
+use super::invalid::Invalid;
+
+const MARKER: &[u8] = b"@cfg(";
+
+/// The maximum number of different predicates in a literal, as every
+/// combination of them is a variant of the literal.
+const MAX_PREDICATES: usize = 6;
+
+/// A `@cfg(...)` marker at the start of a line.
+#[derive(Debug, PartialEq, Eq)]
+struct Marker<'a> {
+    predicate: &'a [u8],
+    /// The byte range of the predicate in the value of the literal
+    range: Range<usize>,
+    /// The rest of the line after the marker
+    rest: &'a [u8],
+}
+
+/// Returns the marker of `line` if it starts with one (after any leading
+/// whitespace), where `offset` is the offset of the line in the value of the
+/// literal.
+fn marker_of(
+    line: &[u8],
+    offset: usize,
+) -> Result<Option<Marker<'_>>, Invalid> {
+    let start = line
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    let Some(rest) = line[start..].strip_prefix(MARKER) else {
+        return Ok(None);
+    };
+    let start = offset + start;
+
+    let mut depth = 1;
+    let mut is_quoted = false;
+    let mut is_escaped = false;
+    for (index, &byte) in rest.iter().enumerate() {
+        match byte {
+            _ if is_escaped => is_escaped = false,
+            b'\\' if is_quoted => is_escaped = true,
+            b'"' => is_quoted = !is_quoted,
+            _ if is_quoted => (),
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let predicate = start + MARKER.len();
+                    return Ok(Some(Marker {
+                        predicate: &rest[..index],
+                        range: predicate..predicate + index,
+                        rest: &rest[index + 1..],
+                    }));
+                }
+            },
+            _ => (),
+        }
+    }
+
+    let range = start..start + MARKER.len();
+    Err(Invalid::new(range, "Unclosed `@cfg(` marker"))
+}
+
+/// Returns `predicate` without the whitespace outside of its string literals,
+/// so that the same predicate is recognized however it is spaced.
+fn normalized(predicate: &[u8]) -> String {
+    let mut normalized = Vec::with_capacity(predicate.len());
+    let mut is_quoted = false;
+    let mut is_escaped = false;
+    for &byte in predicate {
+        match byte {
+            _ if is_escaped => is_escaped = false,
+            b'\\' if is_quoted => is_escaped = true,
+            b'"' => is_quoted = !is_quoted,
+            _ if is_quoted => (),
+            _ if byte.is_ascii_whitespace() => continue,
+            _ => (),
+        }
+        normalized.push(byte);
+    }
+
+    String::from_utf8_lossy(&normalized).into_owned()
+}
+
+/// A `cfg` predicate of the markers of a literal.
+pub struct Predicate {
+    /// The predicate without the whitespace outside of its string literals
+    pub text: String,
+    /// The byte range of its first occurrence in the value of the literal
+    pub range: Range<usize>,
+}
+
+/// The lines of a literal, some of which are only kept if their `cfg`
+/// predicate holds, the same as `cfg_lines` of `trim!`.
+pub struct CfgLines<'a> {
+    /// The index of the predicate of the line (if it has one), and the rest of
+    /// the line including its line break
+    lines: Vec<(Option<usize>, &'a [u8])>,
+    predicates: Vec<Predicate>,
+}
+
+impl<'a> CfgLines<'a> {
+    /// Collects the lines of `value`, i.e. of the (unescaped) value of the
+    /// literal.
+    pub fn new(value: &'a [u8]) -> Result<Self, Invalid> {
+        let mut lines = Vec::new();
+        let mut predicates = Vec::<Predicate>::new();
+        let mut offset = 0;
+
+        for line in value.split_inclusive(|&byte| byte == b'\n') {
+            let marker = marker_of(line, offset)?;
+            offset += line.len();
+            let Some(Marker { predicate, range, rest }) = marker else {
+                lines.push((None, line));
+                continue;
+            };
+
+            let text = normalized(predicate);
+            let index = match predicates.iter().position(|other| {
+                other.text == text
+            }) {
+                Some(index) => index,
+                None if predicates.len() < MAX_PREDICATES => {
+                    predicates.push(Predicate { text, range });
+                    predicates.len() - 1
+                },
+                None => {
+                    let message = format!(
+                        "At most {MAX_PREDICATES} different `cfg` predicates \
+                         can be used in a literal",
+                    );
+                    return Err(Invalid::new(range, message));
+                },
+            };
+            lines.push((Some(index), rest));
+        }
+
+        Ok(Self { lines, predicates })
+    }
+
+    /// Returns the different predicates of the markers, in the order of their
+    /// first occurrence.
+    pub fn predicates(&self) -> &[Predicate] {
+        &self.predicates
+    }
+
+    /// Returns the variant of the literal, where the bits of `holds` are
+    /// whether the corresponding predicates hold.
+    pub fn variant(&self, holds: usize) -> Vec<u8> {
+        self.lines
+            .iter()
+            .filter(|(predicate, _)| {
+                predicate.map_or(true, |index| holds & (1 << index) != 0)
+            })
+            .flat_map(|(_, line)| line.iter().copied())
+            .collect()
+    }
+
+    /// Returns every variant of the literal, where the bits of the index of a
+    /// variant are whether the corresponding predicates hold.
+    pub fn variants(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        (0..1_usize << self.predicates.len()).map(|holds| self.variant(holds))
+    }
+
+    /// Returns the variant of the literal, in which only the predicates among
+    /// `holding` hold (which are compared regardless of their spacing).
+    pub fn selected(&self, holding: &[String]) -> Vec<u8> {
+        let holding: Vec<_> = holding
+            .iter()
+            .map(|predicate| normalized(predicate.as_bytes()))
+            .collect();
+        let holds = self
+            .predicates
+            .iter()
+            .enumerate()
+            .filter(|(_, predicate)| holding.contains(&predicate.text))
+            .fold(0, |holds, (index, _)| holds | (1 << index));
+
+        self.variant(holds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(value: &str) -> Vec<String> {
+        CfgLines::new(value.as_bytes())
+            .ok()
+            .unwrap()
+            .variants()
+            .map(|variant| String::from_utf8(variant).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn markers() {
+        assert_eq!(marker_of(b"a", 0).ok(), Some(None));
+        assert_eq!(
+            marker_of(b"  @cfg(unix) a\n", 3).ok(),
+            Some(Some(Marker {
+                predicate: b"unix",
+                range: 10..14,
+                rest: b" a\n",
+            })),
+        );
+        let line = b"@cfg(any(unix, feature = \"(\\\")\")))a";
+        assert_eq!(
+            marker_of(line, 0).ok(),
+            Some(Some(Marker {
+                predicate: b"any(unix, feature = \"(\\\")\")",
+                range: 5..32,
+                rest: b")a",
+            })),
+        );
+        assert_eq!(marker_of(b" @cfg(any(unix)", 0).unwrap_err().range, 1..6);
+    }
+
+    #[test]
+    fn normalized_predicates() {
+        assert_eq!(normalized(b" all( unix,\ttest )"), "all(unix,test)");
+        assert_eq!(
+            normalized(b"feature = \"a \\\" b\""),
+            "feature=\"a \\\" b\"",
+        );
+    }
+
+    #[test]
+    fn all_variants() {
+        assert_eq!(variants("a\nb"), ["a\nb"]);
+        assert_eq!(
+            variants("a\n@cfg(unix) b\r\n@cfg(test)c\n@cfg( unix )d"),
+            ["a\n", "a\n b\r\nd", "a\nc\n", "a\n b\r\nc\nd"],
+        );
+    }
+
+    #[test]
+    fn selected_variant() {
+        let value = "a\n@cfg(unix) b\n@cfg(any(x, y))c\n";
+        let lines = CfgLines::new(value.as_bytes()).ok().unwrap();
+
+        assert_eq!(lines.selected(&[]), b"a\n");
+        assert_eq!(
+            lines.selected(&[String::from("any(x,  y)")]),
+            b"a\nc\n",
+        );
+    }
+
+    #[test]
+    fn too_many_predicates() {
+        let value = "@cfg(a)\n@cfg(b)\n@cfg(c)\n@cfg(d)\n@cfg(e)\n@cfg(f)\n\
+                     @cfg(a)\n@cfg(g)";
+        let invalid = CfgLines::new(value.as_bytes()).err().unwrap();
+
+        assert_eq!(invalid.range, 61..62);
+    }
+}
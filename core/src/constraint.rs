@@ -0,0 +1,119 @@
+use std::str;
+use super::invalid::character_at;
+
+/// The requirements the trimmed output has to meet, the same as `max_len`,
+/// `min_lines`, `ascii_only`, `forbid`, and `require_utf8` of `trim!`.
+#[derive(Clone, Default)]
+pub struct Constraints {
+    pub max_len: Option<usize>,
+    pub min_lines: Option<usize>,
+    pub ascii_only: bool,
+    pub forbid: Vec<char>,
+    pub require_utf8: bool,
+}
+
+impl Constraints {
+    /// Returns the messages of the requirements the trimmed `bytes` do not
+    /// meet, where `lines` is the number of non-blank lines of the input.
+    pub fn check(&self, bytes: &[u8], lines: usize) -> Vec<String> {
+        [
+            self.check_max_len(bytes),
+            self.check_min_lines(lines),
+            self.check_ascii_only(bytes),
+            self.check_forbid(bytes),
+            self.check_require_utf8(bytes),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect()
+    }
+
+    fn check_max_len(&self, bytes: &[u8]) -> Result<(), String> {
+        match self.max_len {
+            Some(max_len) if bytes.len() > max_len => {
+                let message = format!(
+                    "The trimmed literal is {} bytes long, which exceeds \
+                     `max_len = {max_len}`",
+                    bytes.len(),
+                );
+                Err(message)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn check_min_lines(&self, lines: usize) -> Result<(), String> {
+        match self.min_lines {
+            Some(min_lines) if lines < min_lines => {
+                let message = format!(
+                    "The literal has {lines} non-blank line(s), which is fewer \
+                     than `min_lines = {min_lines}`",
+                );
+                Err(message)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn check_ascii_only(&self, bytes: &[u8]) -> Result<(), String> {
+        if !self.ascii_only {
+            return Ok(());
+        }
+
+        match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(offset) => {
+                let message = format!(
+                    "The trimmed literal contains the non-ASCII character \
+                     `{}` at byte {offset}",
+                    character_at(bytes, offset).0,
+                );
+                Err(message)
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn check_forbid(&self, bytes: &[u8]) -> Result<(), String> {
+        let mut buffer = [0; 4];
+        let found = self
+            .forbid
+            .iter()
+            .filter_map(|character| {
+                let encoded = character.encode_utf8(&mut buffer).as_bytes();
+                bytes
+                    .windows(encoded.len())
+                    .position(|window| window == encoded)
+                    .map(|offset| (offset, character))
+            })
+            .min();
+
+        match found {
+            Some((offset, character)) => {
+                let message = format!(
+                    "The trimmed literal contains the forbidden character \
+                     `{}` at byte {offset}",
+                    character.escape_debug(),
+                );
+                Err(message)
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn check_require_utf8(&self, bytes: &[u8]) -> Result<(), String> {
+        if !self.require_utf8 {
+            return Ok(());
+        }
+
+        match str::from_utf8(bytes) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                let message = format!(
+                    "The trimmed literal is not valid UTF-8 at byte {}",
+                    error.valid_up_to(),
+                );
+                Err(message)
+            },
+        }
+    }
+}
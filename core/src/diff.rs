@@ -0,0 +1,161 @@
+/// The maximum number of entries of the table of the lengths of the longest
+/// common subsequences, beyond which the differing lines are simply listed.
+const MAX_LENGTHS: usize = 1 << 20;
+
+/// Returns `line` with its whitespace made visible, i.e. a space is shown as
+/// `·`, a tab as `→`, a carriage return as `␍`, and any other whitespace
+/// character as its Unicode escape.  (So are the characters used as markers,
+/// so that they cannot be mistaken for whitespace.)
+fn visible(line: &str) -> String {
+    let mut visible = String::with_capacity(line.len());
+    for character in line.chars() {
+        match character {
+            ' ' => visible.push('\u{b7}'),
+            '\t' => visible.push('\u{2192}'),
+            '\r' => visible.push('\u{240d}'),
+            '\u{b7}' | '\u{2192}' | '\u{240d}' => {
+                visible.extend(character.escape_unicode());
+            },
+            _ if character.is_whitespace() => {
+                visible.extend(character.escape_unicode());
+            },
+            _ => visible.push(character),
+        }
+    }
+
+    visible
+}
+
+/// Returns the lines of `expected` and `actual` (which have no common first
+/// or last lines) prefixed by `-` if they are only in `expected`, by `+` if
+/// they are only in `actual`, and by a space if they are in both.
+fn compared<'a>(
+    expected: &[&'a str],
+    actual: &[&'a str],
+) -> Vec<(char, &'a str)> {
+    let removed = expected.iter().map(|&line| ('-', line));
+    let added = actual.iter().map(|&line| ('+', line));
+
+    // NOTE: The lengths of the longest common subsequences take quadratic
+    //       memory, so the lines of large differences are simply listed
+    let width = actual.len() + 1;
+    let size = (expected.len() + 1).checked_mul(width);
+    if size.map_or(true, |size| size > MAX_LENGTHS) {
+        return removed.chain(added).collect();
+    }
+
+    // NOTE: The length of the longest common subsequence of the lines from
+    //       the `i`th expected and the `j`th actual ones is at `i * width + j`
+    let mut lengths = vec![0_usize; (expected.len() + 1) * width];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i * width + j] = if expected[i] == actual[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        let line = match (expected.get(i), actual.get(j)) {
+            (Some(&left), Some(&right)) if left == right => {
+                (i, j) = (i + 1, j + 1);
+                (' ', left)
+            },
+            (Some(&left), Some(_))
+                if lengths[(i + 1) * width + j]
+                    >= lengths[i * width + j + 1] =>
+            {
+                i += 1;
+                ('-', left)
+            },
+            (Some(&left), None) => {
+                i += 1;
+                ('-', left)
+            },
+            (_, Some(&right)) => {
+                j += 1;
+                ('+', right)
+            },
+            (None, None) => break,
+        };
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Compares `expected` and `actual` line by line, and returns the lines of
+/// both with their whitespace made visible, where the lines which are only in
+/// `expected` are prefixed by `-`, the ones which are only in `actual` by `+`,
+/// and the common ones by a space.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.split('\n').collect();
+    let actual: Vec<_> = actual.split('\n').collect();
+
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+
+    let (end, actual_end) = (expected.len() - suffix, actual.len() - suffix);
+    let differing =
+        compared(&expected[prefix..end], &actual[prefix..actual_end]);
+    let lines: Vec<_> = expected[..prefix]
+        .iter()
+        .map(|&line| (' ', line))
+        .chain(differing)
+        .chain(expected[end..].iter().map(|&line| (' ', line)))
+        .map(|(sign, line)| format!("{sign} {}", visible(line)))
+        .collect();
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(visible("a b\tc\r"), "a\u{b7}b\u{2192}c\u{240d}");
+        assert_eq!(visible("a\u{a0}b"), "a\\u{a0}b");
+        assert_eq!(
+            visible("\u{b7}\u{2192}\u{240d}"),
+            "\\u{b7}\\u{2192}\\u{240d}",
+        );
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(diff("a\nb", "a\nb"), "  a\n  b");
+        assert_eq!(
+            diff("a\nb\nc", "a\nb \nc\nd"),
+            "  a\n- b\n+ b\u{b7}\n  c\n+ d",
+        );
+        assert_eq!(diff("a\r\nb", "a\nb"), "- a\u{240d}\n+ a\n  b");
+        assert_eq!(diff("a\n", "a"), "  a\n- ");
+        assert_eq!(diff("", "a"), "- \n+ a");
+        assert_eq!(diff("a\nb\nb", "a\nb"), "  a\n  b\n- b");
+    }
+
+    #[test]
+    fn large() {
+        let expected = "x\n".repeat(2000);
+        let actual = "y\n".repeat(2000);
+        let diff = diff(&expected, &actual);
+        assert_eq!(diff.lines().filter(|line| line == &"- x").count(), 2000);
+        assert_eq!(diff.lines().filter(|line| line == &"+ y").count(), 2000);
+        assert!(diff.starts_with("- x\n") && diff.ends_with("+ y\n  "));
+    }
+}
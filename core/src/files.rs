@@ -0,0 +1,56 @@
+use std::{
+    fs,
+    io,
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// Returns the paths (relative to `directory`) of all the files in `directory`
+/// (including the ones in its subdirectories) for whose path `filter` returns
+/// `true`, sorted.  Symbolic links are followed, but a directory is only walked
+/// once, even if it is linked to from inside of itself.  If a directory or a
+/// file cannot be read, the error is returned with its path.
+pub fn files_in(
+    directory: &Path,
+    filter: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, (PathBuf, io::Error)> {
+    let mut files = Vec::new();
+    let mut directories = vec![PathBuf::new()];
+    let mut walked = HashSet::new();
+
+    while let Some(relative) = directories.pop() {
+        let current = directory.join(&relative);
+        let canonical = match fs::canonicalize(&current) {
+            Ok(canonical) => canonical,
+            Err(error) => return Err((current, error)),
+        };
+        if !walked.insert(canonical) {
+            continue;
+        }
+
+        let entries = fs::read_dir(&current)
+            .and_then(|entries| entries.collect::<io::Result<Vec<_>>>())
+            .map_err(|error| (current, error))?;
+
+        for entry in entries {
+            let path = entry.path();
+            let relative = relative.join(entry.file_name());
+            // NOTE: The type of the entry itself does not tell whether a
+            //       symbolic link points to a directory, but its metadata does
+            let file_type = match fs::metadata(&path) {
+                Ok(metadata) => metadata.file_type(),
+                Err(error) => return Err((path, error)),
+            };
+
+            if file_type.is_dir() {
+                directories.push(relative);
+            } else if filter(&path) {
+                files.push(relative);
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
@@ -0,0 +1,242 @@
+use super::invalid::Invalid;
+
+const BLOCK_QUOTE: &[u8] = b"\"\"\"";
+const ESCAPED_BLOCK_QUOTE: &[u8] = b"\\\"\"\"";
+
+fn is_name_start(byte: u8) -> bool {
+    byte == b'_' || byte.is_ascii_alphabetic()
+}
+
+fn is_name_continue(byte: u8) -> bool {
+    byte == b'_' || byte.is_ascii_alphanumeric()
+}
+
+fn closing_of(opening: u8) -> u8 {
+    match opening {
+        b'{' => b'}',
+        b'(' => b')',
+        _ => b']',
+    }
+}
+
+fn skip_digits(bytes: &[u8], mut index: usize) -> usize {
+    while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+        index += 1;
+    }
+
+    index
+}
+
+/// Removes all comments, commas, and redundant whitespace from a GraphQL
+/// document, while keeping the content of strings and block-strings intact.
+/// It also makes sure that braces, parentheses, and brackets are balanced.
+pub fn minify(document: &str) -> Result<String, Invalid> {
+    let bytes = document.as_bytes();
+    let mut minified = String::with_capacity(document.len());
+    let mut opened = Vec::new();
+    // Two adjacent names or numbers must be separated by a single space
+    let mut previous_is_word = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        let byte = bytes[index];
+
+        let end = match byte {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => {
+                index += 1;
+                continue;
+            },
+            b'#' => {
+                while index < bytes.len()
+                    && bytes[index] != b'\n'
+                    && bytes[index] != b'\r'
+                {
+                    index += 1;
+                }
+                continue;
+            },
+            b'"' if bytes[start..].starts_with(BLOCK_QUOTE) => {
+                let mut end = start + BLOCK_QUOTE.len();
+                loop {
+                    let rest = &bytes[end..];
+                    if rest.is_empty() {
+                        let message = "Unterminated block-string";
+                        return Err(Invalid::new(start..end, message));
+                    } else if rest.starts_with(ESCAPED_BLOCK_QUOTE) {
+                        end += ESCAPED_BLOCK_QUOTE.len();
+                    } else if rest.starts_with(BLOCK_QUOTE) {
+                        break end + BLOCK_QUOTE.len();
+                    } else {
+                        end += 1;
+                    }
+                }
+            },
+            b'"' => {
+                let mut end = start + 1;
+                loop {
+                    match bytes.get(end) {
+                        None | Some(b'\n' | b'\r') => {
+                            let end = end.min(bytes.len());
+                            let message = "Unterminated string";
+                            return Err(Invalid::new(start..end, message));
+                        },
+                        Some(b'\\') => end += 2,
+                        Some(b'"') => break end + 1,
+                        Some(_) => end += 1,
+                    }
+                }
+            },
+            b'{' | b'(' | b'[' => {
+                opened.push(start);
+                start + 1
+            },
+            b'}' | b')' | b']' => {
+                let Some(opening_i) = opened.pop() else {
+                    let message =
+                        format!("Unmatched closing `{}`", byte as char);
+                    return Err(Invalid::new(start..start + 1, message));
+                };
+
+                let expected = closing_of(bytes[opening_i]);
+                if byte != expected {
+                    let message = format!(
+                        "Mismatched closing `{}`, expected `{}`",
+                        byte as char,
+                        expected as char,
+                    );
+                    return Err(Invalid::new(start..start + 1, message));
+                }
+
+                start + 1
+            },
+            b'.' if bytes[start..].starts_with(b"...") => start + 3,
+            b'!' | b'$' | b'&' | b':' | b'=' | b'@' | b'|' => start + 1,
+            byte if is_name_start(byte) => {
+                let mut end = start + 1;
+                while bytes.get(end).copied().is_some_and(is_name_continue) {
+                    end += 1;
+                }
+
+                end
+            },
+            b'-' | b'0'..=b'9' => {
+                let mut end = start + usize::from(byte == b'-');
+                end = skip_digits(bytes, end);
+                if bytes.get(end) == Some(&b'.') {
+                    end = skip_digits(bytes, end + 1);
+                }
+                if let Some(b'e' | b'E') = bytes.get(end) {
+                    end += 1;
+                    if let Some(b'+' | b'-') = bytes.get(end) {
+                        end += 1;
+                    }
+                    end = skip_digits(bytes, end);
+                }
+
+                end
+            },
+            _ => {
+                let character = document[start..]
+                    .chars()
+                    .next()
+                    .expect("Index is at a character boundary");
+                let end = start + character.len_utf8();
+                let message = format!(
+                    "Unexpected character `{}`",
+                    character.escape_debug(),
+                );
+                return Err(Invalid::new(start..end, message));
+            },
+        };
+
+        let is_word = is_name_continue(byte) || byte == b'-';
+        let needs_space = previous_is_word && (is_word || byte == b'.');
+        if needs_space {
+            minified.push(' ');
+        }
+        minified.push_str(&document[start..end]);
+
+        previous_is_word = is_word;
+        index = end;
+    }
+
+    if let Some(opening_i) = opened.pop() {
+        let message = format!("Unclosed `{}`", bytes[opening_i] as char);
+        return Err(Invalid::new(opening_i..opening_i + 1, message));
+    }
+
+    Ok(minified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minified(document: &str) -> String {
+        minify(document)
+            .unwrap_or_else(|invalid| panic!("{}", invalid.message))
+    }
+
+    #[test]
+    fn removes_comments_commas_and_whitespace() {
+        let document = "
+            # Fetches a hero
+            query Hero($episode: Episode = JEDI, $limit: Int) {
+                hero(episode: $episode) {  # inline comment
+                    name,
+                    friends(first: $limit) { name }
+                }
+            }
+        ";
+
+        assert_eq!(
+            minified(document),
+            "query Hero($episode:Episode=JEDI$limit:Int){hero(episode:$episode)\
+             {name friends(first:$limit){name}}}",
+        );
+    }
+
+    #[test]
+    fn keeps_strings_and_block_strings_intact() {
+        let document = r#"
+            mutation {
+                a(x: "  # not, a comment  ", y: """
+                    keep   this # too, \""" and this
+                """)
+            }
+        "#;
+
+        assert_eq!(
+            minified(document),
+            r#"mutation{a(x:"  # not, a comment  "y:"""
+                    keep   this # too, \""" and this
+                """)}"#,
+        );
+    }
+
+    #[test]
+    fn separates_adjacent_words() {
+        assert_eq!(minified("f(a: -1 b: 2.5e-3) { ...on T { x } }"),
+                   "f(a:-1 b:2.5e-3){...on T{x}}");
+        assert_eq!(minified("f(a: [1 2 3])"), "f(a:[1 2 3])");
+    }
+
+    #[test]
+    fn unbalanced() {
+        let invalid = minify("query { a ( }").err().unwrap();
+        assert_eq!(invalid.range, 12..13);
+
+        let invalid = minify("query { a").err().unwrap();
+        assert_eq!(invalid.range, 6..7);
+
+        let invalid = minify("query { a } }").err().unwrap();
+        assert_eq!(invalid.range, 12..13);
+    }
+
+    #[test]
+    fn unterminated_strings() {
+        assert_eq!(minify("a(b: \"c\n\")").err().unwrap().range, 5..7);
+        assert_eq!(minify("a(b: \"\"\"c)").err().unwrap().range, 5..10);
+    }
+}
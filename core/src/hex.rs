@@ -0,0 +1,138 @@
+use super::{bytes::Bytes as _, invalid::Invalid};
+
+const DIGIT: &str = "hexadecimal digit";
+
+fn nibble_of(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Whether `word` is an offset as printed by `hexdump -C`, i.e. 8 digits.
+fn is_hexdump_offset(word: &[u8]) -> bool {
+    word.len() == 8 && word.iter().all(u8::is_ascii_hexdigit)
+}
+
+/// Decodes hexadecimal digits of already trimmed `lines` into bytes.  Digits
+/// can be separated by any whitespace, and they can have an optional `0x` or
+/// `0X` prefix.  If the first whitespace-separated word of a line ends with a
+/// `:`, it is considered an offset column and ignored.
+///
+/// The output of `hexdump -C` is recognized as well, i.e. if a line starts
+/// with 8 digits followed by two spaces, they are its offset column, and the
+/// rest of the line from a `|` on is its ASCII column, both of which are
+/// ignored, just like a line with only the final offset after such lines.
+pub fn decode<'a>(
+    value: &'a [u8],
+    lines: impl Iterator<Item = &'a [u8]>,
+) -> Result<Vec<u8>, Invalid> {
+    let mut decoded = Vec::with_capacity(value.len() / 2);
+    let mut is_hexdump = false;
+
+    for mut line in lines {
+        let offset = line.get(..8).filter(|offset| is_hexdump_offset(offset));
+        if offset.is_some() && line.get(8..10) == Some(b"  ") {
+            is_hexdump = true;
+            line = &line[8..];
+            if let Some(end) = line.iter().position(|&byte| byte == b'|') {
+                line = &line[..end];
+            }
+        } else if is_hexdump && is_hexdump_offset(line) {
+            continue;
+        }
+
+        let words = line
+            .split(u8::is_ascii_whitespace)
+            .filter(|word| !word.is_empty());
+
+        for (i, word) in words.enumerate() {
+            if i == 0 && word.ends_with(b":") {
+                continue;
+            }
+
+            let digits = word
+                .strip_prefix(b"0x")
+                .or_else(|| word.strip_prefix(b"0X"))
+                .unwrap_or(word);
+            let offset = value.offset_of(digits);
+
+            let mut pairs = digits.chunks_exact(2);
+            for (j, pair) in pairs.by_ref().enumerate() {
+                let [high, low] = [pair[0], pair[1]].map(nibble_of);
+                let (Some(high), Some(low)) = (high, low) else {
+                    let k = offset + 2*j + usize::from(high.is_some());
+                    return Err(Invalid::unexpected(value, k, DIGIT));
+                };
+
+                decoded.push((high << 4) | low);
+            }
+
+            if let [digit] = pairs.remainder() {
+                let k = offset + digits.len() - 1;
+                if nibble_of(*digit).is_none() {
+                    return Err(Invalid::unexpected(value, k, DIGIT));
+                }
+
+                let message = "Odd number of hexadecimal digits";
+                return Err(Invalid::new(offset..k + 1, message));
+            }
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte_string::Lines;
+
+    fn decoded(value: &[u8]) -> Result<Vec<u8>, Invalid> {
+        decode(value, Lines::from(value))
+    }
+
+    #[test]
+    fn plain_and_prefixed_digits() {
+        let value = b"
+            48 65 6c 6C 6f
+            0x2c 0X20 576f726c6421
+        ";
+
+        assert_eq!(decoded(value).ok().unwrap(), b"Hello, World!");
+    }
+
+    #[test]
+    fn offset_columns() {
+        let value = b"
+            00000000: 48 65 6c 6c
+            00000004: 6f
+        ";
+
+        assert_eq!(decoded(value).ok().unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn hexdump_columns() {
+        let value = concat!(
+            "00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a  ",
+            "|Hello, World!.|\n",
+            "0000000e",
+        );
+
+        assert_eq!(decoded(value.as_bytes()).ok().unwrap(), b"Hello, World!\n");
+        assert_eq!(decoded(b"48656c6c 6f").ok().unwrap(), b"Hello");
+        assert_eq!(decoded(b"48656c6c").ok().unwrap(), b"Hell");
+    }
+
+    #[test]
+    fn invalid_digits() {
+        assert_eq!(decoded(b"48 6g").err().unwrap().range, 4..5);
+        assert_eq!(decoded(b"48 g6").err().unwrap().range, 3..4);
+        assert_eq!(decoded(b"48 656").err().unwrap().range, 3..6);
+        assert_eq!(decoded(b"48 65z").err().unwrap().range, 5..6);
+        assert_eq!(decoded("48 6\u{e9}".as_bytes()).err().unwrap().range, 4..6);
+    }
+}
@@ -0,0 +1,54 @@
+use std::{str, ops::Range};
+
+/// Returns the (possibly multi-byte) character of `value` starting at
+/// `offset` escaped for an error message, and its length in bytes.
+pub fn character_at(value: &[u8], offset: usize) -> (String, usize) {
+    let rest = &value[offset..];
+    let character = (1..=rest.len().min(4))
+        .find_map(|length| str::from_utf8(&rest[..length]).ok())
+        .and_then(|string| string.chars().next());
+
+    match character {
+        Some(character) => {
+            (character.escape_debug().to_string(), character.len_utf8())
+        },
+        None => (format!("\\x{:02x}", value[offset]), 1),
+    }
+}
+
+/// Describes a problem with the content of a literal, where `range` is the
+/// byte range of the offending part of the literal's (unescaped) value.
+pub struct Invalid {
+    pub range: Range<usize>,
+    pub message: String,
+}
+
+impl Invalid {
+    pub fn new(range: Range<usize>, message: impl Into<String>) -> Self {
+        Self { range, message: message.into() }
+    }
+
+    /// Creates an error describing the (possibly multi-byte) character of
+    /// `value` starting at `offset`.
+    pub fn unexpected(value: &[u8], offset: usize, what: &str) -> Self {
+        let (character, length) = character_at(value, offset);
+        let message = format!("Invalid {what} `{character}`");
+        Self::new(offset..offset + length, message)
+    }
+
+    /// Returns the message extended with the line and the column of the
+    /// offending part, where `value` is the literal's (unescaped) value.
+    pub fn located(&self, value: &[u8]) -> String {
+        let before = &value[..self.range.start];
+        let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+        let line_start = before
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |index| index + 1);
+        let column = String::from_utf8_lossy(&before[line_start..])
+            .chars()
+            .count() + 1;
+
+        format!("{} (at line {line}, column {column})", self.message)
+    }
+}
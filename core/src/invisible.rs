@@ -0,0 +1,139 @@
+use std::{str, ops::RangeInclusive};
+use super::invalid::Invalid;
+
+/// Ranges of characters which are invisible or change the direction of the
+/// text, so that they can make the literal look different from what it really
+/// is, e.g. the tag characters can smuggle invisible ASCII text into it.
+const INVISIBLES: [(RangeInclusive<char>, &str); 33] = [
+    ('\u{00ad}'..='\u{00ad}', "soft hyphen"),
+    ('\u{034f}'..='\u{034f}', "combining grapheme joiner"),
+    ('\u{061c}'..='\u{061c}', "arabic letter mark"),
+    ('\u{115f}'..='\u{115f}', "hangul choseong filler"),
+    ('\u{1160}'..='\u{1160}', "hangul jungseong filler"),
+    ('\u{17b4}'..='\u{17b4}', "khmer vowel inherent aq"),
+    ('\u{17b5}'..='\u{17b5}', "khmer vowel inherent aa"),
+    ('\u{180b}'..='\u{180d}', "mongolian free variation selector"),
+    ('\u{180e}'..='\u{180e}', "mongolian vowel separator"),
+    ('\u{200b}'..='\u{200b}', "zero width space"),
+    ('\u{200c}'..='\u{200c}', "zero width non-joiner"),
+    ('\u{200d}'..='\u{200d}', "zero width joiner"),
+    ('\u{200e}'..='\u{200e}', "left-to-right mark"),
+    ('\u{200f}'..='\u{200f}', "right-to-left mark"),
+    ('\u{202a}'..='\u{202a}', "left-to-right embedding"),
+    ('\u{202b}'..='\u{202b}', "right-to-left embedding"),
+    ('\u{202c}'..='\u{202c}', "pop directional formatting"),
+    ('\u{202d}'..='\u{202d}', "left-to-right override"),
+    ('\u{202e}'..='\u{202e}', "right-to-left override"),
+    ('\u{2060}'..='\u{2060}', "word joiner"),
+    ('\u{2061}'..='\u{2061}', "function application"),
+    ('\u{2062}'..='\u{2062}', "invisible times"),
+    ('\u{2063}'..='\u{2063}', "invisible separator"),
+    ('\u{2064}'..='\u{2064}', "invisible plus"),
+    ('\u{2066}'..='\u{2066}', "left-to-right isolate"),
+    ('\u{2067}'..='\u{2067}', "right-to-left isolate"),
+    ('\u{2068}'..='\u{2068}', "first strong isolate"),
+    ('\u{2069}'..='\u{2069}', "pop directional isolate"),
+    ('\u{3164}'..='\u{3164}', "hangul filler"),
+    ('\u{fe00}'..='\u{fe0f}', "variation selector"),
+    ('\u{feff}'..='\u{feff}', "zero width no-break space"),
+    ('\u{e0000}'..='\u{e007f}', "tag character"),
+    ('\u{e0100}'..='\u{e01ef}', "variation selector supplement"),
+];
+
+/// Returns the length of the UTF-8 sequence `byte` is the first byte of.
+fn sequence_length(byte: u8) -> usize {
+    match byte.leading_ones() {
+        2 => 2,
+        3 => 3,
+        4 => 4,
+        _ => 1,
+    }
+}
+
+/// Checks whether the valid UTF-8 parts of `value` contain any characters
+/// which are invisible or which change the direction of the text.
+pub fn check(value: &[u8]) -> Result<(), Invalid> {
+    let mut index = 0;
+
+    while index < value.len() {
+        let length = sequence_length(value[index]);
+        let character = value
+            .get(index..index + length)
+            .and_then(|bytes| str::from_utf8(bytes).ok())
+            .and_then(|string| string.chars().next());
+
+        let Some(character) = character else {
+            index += 1;
+            continue;
+        };
+
+        let invisible = INVISIBLES
+            .iter()
+            .find(|(invisibles, _)| invisibles.contains(&character));
+        if let Some((_, name)) = invisible {
+            let message = format!(
+                "Invisible character `U+{:04X}` ({name})",
+                u32::from(character),
+            );
+            return Err(Invalid::new(index..index + length, message));
+        }
+
+        index += length;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible() {
+        assert!(check("Hello, W\u{f6}rld! \u{1f30d}".as_bytes()).is_ok());
+        assert!(check(b"\xff\xe2\x80").is_ok());
+        assert!(check(b"\t\r\n").is_ok());
+    }
+
+    #[test]
+    fn invisible() {
+        let invalid = check("a\u{200b}b".as_bytes()).err().unwrap();
+        assert_eq!(invalid.range, 1..4);
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+200B` (zero width space)",
+        );
+
+        let invalid = check(b"\xff\xe2\x80\xae").err().unwrap();
+        assert_eq!(invalid.range, 1..4);
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+202E` (right-to-left override)",
+        );
+
+        let invalid = check("\u{ad}".as_bytes()).err().unwrap();
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+00AD` (soft hyphen)",
+        );
+
+        let invalid = check("ab\u{e0041}".as_bytes()).err().unwrap();
+        assert_eq!(invalid.range, 2..6);
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+E0041` (tag character)",
+        );
+
+        let invalid = check("\u{2764}\u{fe0f}".as_bytes()).err().unwrap();
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+FE0F` (variation selector)",
+        );
+
+        let invalid = check("\u{1820}\u{180b}".as_bytes()).err().unwrap();
+        assert_eq!(
+            invalid.message,
+            "Invisible character `U+180B` (mongolian free variation selector)",
+        );
+    }
+}
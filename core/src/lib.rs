@@ -0,0 +1,85 @@
+//! The trimming and the other transformations shared by the `strim` macros and
+//! the `strim` command-line tool, so that both produce the very same output.
+
+pub mod bytes;
+pub mod string;
+pub mod c_string;
+pub mod byte_string;
+pub mod invalid;
+pub mod invisible;
+pub mod graphql;
+pub mod regex;
+pub mod hex;
+pub mod base64;
+pub mod pem;
+pub mod preview;
+pub mod constraint;
+pub mod vars;
+pub mod cfg;
+pub mod lint;
+pub mod transform;
+pub mod files;
+pub mod select;
+pub mod diff;
+
+/// Joins the trimmed, non-blank lines of `string` together with `delimiter`.
+pub fn trimmed_string_joined_with_delimiter(
+    string: &str,
+    delimiter: &str,
+) -> String {
+    let mut collected = String::with_capacity(string.len());
+    let mut lines = string::Lines::from(string);
+
+    if let Some(line) = lines.next() {
+        collected.push_str(line);
+
+        for line in lines {
+            collected.push_str(delimiter);
+            collected.push_str(line);
+        }
+    }
+
+    collected
+}
+
+/// Joins the trimmed, non-blank lines of `byte_string` together with
+/// `delimiter`.
+pub fn trimmed_byte_string_joined_with_delimiter(
+    byte_string: &[u8],
+    delimiter: &[u8],
+) -> Vec<u8> {
+    let mut collected = Vec::with_capacity(byte_string.len());
+    let mut lines = byte_string::Lines::from(byte_string);
+
+    if let Some(line) = lines.next() {
+        collected.extend(line);
+
+        for line in lines {
+            collected.extend(delimiter);
+            collected.extend(line);
+        }
+    }
+
+    collected
+}
+
+/// Joins the trimmed, non-blank lines of the content of `c_string` (i.e.
+/// without its nul-terminator) together with `delimiter`.
+pub fn trimmed_c_string_joined_with_delimiter(
+    c_string: &[u8],
+    delimiter: &[u8],
+) -> Vec<u8> {
+    let mut collected = Vec::with_capacity(c_string.len());
+    let mut lines = c_string::Lines::from(c_string);
+
+    if let Some(line) = lines.next() {
+        collected.extend(line);
+
+        for line in lines {
+            collected.extend(delimiter);
+            collected.extend(line);
+        }
+    }
+
+    collected
+}
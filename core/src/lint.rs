@@ -0,0 +1,150 @@
+use std::ops::Range;
+
+/// The names of the lints, by which they can be allowed.
+pub const NAMES: [&str; 5] = [
+    "needless_join_with",
+    "unchanged",
+    "mixed_indentation",
+    "lone_carriage_return",
+    "trailing_backslash",
+];
+
+/// A suspicious, but otherwise valid input, which is reported as a warning.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Lint {
+    NeedlessJoinWith,
+    Unchanged,
+    MixedIndentation,
+    LoneCarriageReturn,
+    TrailingBackslash,
+}
+
+impl Lint {
+    pub const ALL: [Self; 5] = [
+        Self::NeedlessJoinWith,
+        Self::Unchanged,
+        Self::MixedIndentation,
+        Self::LoneCarriageReturn,
+        Self::TrailingBackslash,
+    ];
+
+    pub fn name(self) -> &'static str {
+        NAMES[self as usize]
+    }
+
+    /// Returns the lint called `name`, if there is one.
+    pub fn named(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::NeedlessJoinWith => {
+                "`join_with` has no effect, as there is at most one non-blank \
+                 line in the literal"
+            },
+            Self::Unchanged => "`trim!` does not change the literal",
+            Self::MixedIndentation => "Indentation mixes tabs and spaces",
+            Self::LoneCarriageReturn => {
+                "Carriage return (`\\r`) is not followed by a line feed \
+                 (`\\n`), therefore it is not considered as a line break"
+            },
+            Self::TrailingBackslash => {
+                "Line ends with a backslash (`\\`), which is not a line \
+                 continuation, therefore it is kept as it is"
+            },
+        }
+    }
+}
+
+/// Returns the byte ranges of the suspicious parts of `value`, which do not
+/// depend on any of the named parameters.
+pub fn content_lints(value: &[u8]) -> Vec<(Lint, Range<usize>)> {
+    let mut lints = Vec::new();
+    let mut indentation = None;
+    let mut is_mixed = false;
+    let mut start = 0;
+
+    for line in value.split(|&byte| byte == b'\n') {
+        let end = start + line.len();
+        let is_last = end == value.len();
+
+        for (offset, &byte) in line.iter().enumerate() {
+            if is_mixed || (byte != b' ' && byte != b'\t') {
+                break;
+            }
+
+            let index = start + offset;
+            match indentation {
+                None => indentation = Some(byte),
+                Some(first) if first != byte => {
+                    lints.push((Lint::MixedIndentation, index..index + 1));
+                    // NOTE: Only the first occurrence is reported
+                    is_mixed = true;
+                },
+                Some(_) => (),
+            }
+        }
+
+        for (offset, &byte) in line.iter().enumerate() {
+            let index = start + offset;
+            let is_line_break = index + 1 == end && !is_last;
+            if byte == b'\r' && !is_line_break {
+                lints.push((Lint::LoneCarriageReturn, index..index + 1));
+            }
+        }
+
+        let trimmed = line
+            .iter()
+            .rposition(|byte| !byte.is_ascii_whitespace())
+            .map(|last| start + last);
+        if let Some(last) = trimmed.filter(|&last| value[last] == b'\\') {
+            lints.push((Lint::TrailingBackslash, last..last + 1));
+        }
+
+        start = end + 1;
+    }
+
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(value: &str) -> Vec<(&'static str, Range<usize>)> {
+        content_lints(value.as_bytes())
+            .into_iter()
+            .map(|(lint, range)| (lint.name(), range))
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        assert_eq!(lints("\n    a\n        b\n"), []);
+        assert_eq!(lints("\n\ta\n\t\tb\r\n"), []);
+        assert_eq!(lints("a \\ b"), []);
+    }
+
+    #[test]
+    fn mixed_indentation() {
+        assert_eq!(lints("\n    a\n\tb\n\tc"), [("mixed_indentation", 7..8)]);
+        assert_eq!(lints(" \ta"), [("mixed_indentation", 1..2)]);
+    }
+
+    #[test]
+    fn lone_carriage_returns() {
+        assert_eq!(
+            lints("a\rb\r\nc\r"),
+            [("lone_carriage_return", 1..2), ("lone_carriage_return", 6..7)],
+        );
+    }
+
+    #[test]
+    fn trailing_backslashes() {
+        assert_eq!(
+            lints("a \\\n b\\  \n"),
+            [("trailing_backslash", 2..3), ("trailing_backslash", 6..7)],
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::{
+    bytes::Bytes as _,
+    base64::{self, Alphabet},
+    invalid::Invalid,
+};
+
+const DASHES: &[u8] = b"-----";
+
+fn label_of<'a>(line: &'a [u8], kind: &[u8]) -> Option<&'a [u8]> {
+    line.strip_prefix(DASHES)?
+        .strip_prefix(kind)?
+        .strip_prefix(b" ")?
+        .strip_suffix(DASHES)
+}
+
+fn check_label(
+    value: &[u8],
+    line: &[u8],
+    kind: &str,
+    expected: &str,
+) -> Result<(), Invalid> {
+    let offset = value.offset_of(line);
+    let range = offset..offset + line.len();
+
+    match label_of(line, kind.as_bytes()) {
+        Some(label) if label == expected.as_bytes() => Ok(()),
+        Some(label) => {
+            let message = format!(
+                "Expected PEM label `{expected}`, but found `{}`",
+                String::from_utf8_lossy(label),
+            );
+            Err(Invalid::new(range, message))
+        },
+        None => {
+            let message = format!("Expected `-----{kind} {expected}-----`");
+            Err(Invalid::new(range, message))
+        },
+    }
+}
+
+/// Decodes the base64 encoded content of a single PEM block whose already
+/// trimmed `lines` must start with `-----BEGIN <label>-----` and end with
+/// `-----END <label>-----`.
+pub fn decode<'a>(
+    value: &'a [u8],
+    lines: impl Iterator<Item = &'a [u8]>,
+    label: &str,
+) -> Result<Vec<u8>, Invalid> {
+    let lines: Vec<_> = lines.collect();
+
+    let [begin, content @ .., end] = lines.as_slice() else {
+        let message = format!(
+            "Expected a PEM block between `-----BEGIN {label}-----` and \
+             `-----END {label}-----`",
+        );
+        return Err(Invalid::new(0..value.len(), message));
+    };
+
+    check_label(value, begin, "BEGIN", label)?;
+    check_label(value, end, "END", label)?;
+
+    base64::decode(value, content.iter().copied(), Alphabet::Standard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte_string::Lines;
+
+    fn decoded(value: &[u8], label: &str) -> Result<Vec<u8>, Invalid> {
+        decode(value, Lines::from(value), label)
+    }
+
+    #[test]
+    fn valid() {
+        let value = b"
+            -----BEGIN MESSAGE-----
+            SGVsbG8s
+            IFdvcmxkIQ==
+            -----END MESSAGE-----
+        ";
+
+        assert_eq!(decoded(value, "MESSAGE").ok().unwrap(), b"Hello, World!");
+    }
+
+    #[test]
+    fn invalid_labels() {
+        let value = b"-----BEGIN A-----\nSGVs\n-----END B-----";
+        assert_eq!(decoded(value, "A").err().unwrap().range, 23..38);
+        assert_eq!(decoded(value, "B").err().unwrap().range, 0..17);
+
+        let value = b"BEGIN A\nSGVs\n-----END A-----";
+        assert_eq!(decoded(value, "A").err().unwrap().range, 0..7);
+
+        let value = b"-----BEGIN A-----";
+        assert_eq!(decoded(value, "A").err().unwrap().range, 0..17);
+    }
+}
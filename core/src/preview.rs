@@ -0,0 +1,126 @@
+use std::str;
+
+/// Escapes `bytes` so that all the whitespace and control characters in them
+/// become visible.
+pub fn escaped(bytes: &[u8]) -> String {
+    match str::from_utf8(bytes) {
+        Ok(string) => string.escape_debug().to_string(),
+        Err(_) => bytes.escape_ascii().to_string(),
+    }
+}
+
+/// Returns `output` escaped, with its delimiters marked as `delimiter`, if it
+/// consists of `parts` joined together with the same delimiter.
+fn marked(parts: &[&[u8]], output: &[u8], delimiter: &str) -> Option<String> {
+    let length: usize = parts.iter().map(|part| part.len()).sum();
+    let rest = output.len().checked_sub(length)?;
+    let gap = match (parts.len().saturating_sub(1), rest) {
+        (0, 0) => 0,
+        (gaps, rest) if gaps > 0 && rest % gaps == 0 => rest / gaps,
+        _ => return None,
+    };
+
+    let mut rest = output;
+    let mut separator = None;
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            let (gap, after) = rest.split_at(gap);
+            if *separator.get_or_insert(gap) != gap {
+                return None;
+            }
+            rest = after;
+        }
+        rest = rest.strip_prefix(*part)?;
+    }
+
+    let parts: Vec<_> = parts.iter().map(|part| escaped(part)).collect();
+    Some(parts.join(&format!("\u{27e8}{delimiter}\u{27e9}")))
+}
+
+/// Describes how each of the `lines` (pairs of a line as it is in the literal
+/// and as it is after trimming) are trimmed, and what the `output` is.  If the
+/// lines are joined together, `delimiter` is the already escaped delimiter,
+/// which is marked in the output, as long as the output is still made up of
+/// the trimmed lines (e.g. unless variables have been substituted in them),
+/// otherwise the output is shown as it is.
+pub fn preview<'a>(
+    lines: impl Iterator<Item = (&'a [u8], &'a [u8])>,
+    delimiter: Option<&str>,
+    output: &[u8],
+) -> String {
+    let mut preview = String::from("preview of the trimmed literal:");
+    let mut trimmed = Vec::new();
+
+    for (number, (line, trimmed_line)) in lines.enumerate() {
+        preview.push_str(&format!(
+            "\nline {}: {} -> {} bytes",
+            number + 1,
+            line.len(),
+            trimmed_line.len(),
+        ));
+        if trimmed_line.is_empty() {
+            preview.push_str(" (blank, skipped)");
+        } else {
+            preview.push_str(&format!(": \"{}\"", escaped(trimmed_line)));
+            trimmed.push(trimmed_line);
+        }
+    }
+
+    let shown = delimiter
+        .and_then(|delimiter| marked(&trimmed, output, delimiter))
+        .unwrap_or_else(|| escaped(output));
+    preview.push_str(&format!(
+        "\noutput: {} bytes: \"{shown}\"",
+        output.len(),
+    ));
+
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(value: &str) -> impl Iterator<Item = (&[u8], &[u8])> {
+        value.lines().map(|line| (line.as_bytes(), line.trim().as_bytes()))
+    }
+
+    #[test]
+    fn joined() {
+        assert_eq!(
+            preview(
+                lines("\n  Hello,\n\tW\u{f6}rld! \n"),
+                Some(" "),
+                "Hello, W\u{f6}rld!".as_bytes(),
+            ),
+            "preview of the trimmed literal:\n\
+             line 1: 0 -> 0 bytes (blank, skipped)\n\
+             line 2: 8 -> 6 bytes: \"Hello,\"\n\
+             line 3: 9 -> 7 bytes: \"W\u{f6}rld!\"\n\
+             output: 14 bytes: \"Hello,\u{27e8} \u{27e9}W\u{f6}rld!\"",
+        );
+    }
+
+    #[test]
+    fn substituted() {
+        assert_eq!(
+            preview(lines("\n  Hello,\n  {name}!"), Some(" "), b"Hello, Bob!"),
+            "preview of the trimmed literal:\n\
+             line 1: 0 -> 0 bytes (blank, skipped)\n\
+             line 2: 8 -> 6 bytes: \"Hello,\"\n\
+             line 3: 9 -> 7 bytes: \"{name}!\"\n\
+             output: 11 bytes: \"Hello, Bob!\"",
+        );
+    }
+
+    #[test]
+    fn not_joined() {
+        assert_eq!(
+            preview(lines("a\t\nb"), None, b"a\tb\xff"),
+            "preview of the trimmed literal:\n\
+             line 1: 2 -> 1 bytes: \"a\"\n\
+             line 2: 1 -> 1 bytes: \"b\"\n\
+             output: 4 bytes: \"a\\tb\\xff\"",
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use super::invalid::Invalid;
+
+/// Turns a verbose regular expression into a compact one, i.e. removes all
+/// unescaped whitespace and `#` comments outside of character classes, while
+/// keeping escaped characters and the contents of character classes intact.
+///
+/// Nested character classes (e.g. `[a-z&&[^aeiou]]` or `[[:alpha:]]`) are
+/// tracked the same way as the `regex` crate does.
+pub fn compact(pattern: &str) -> Result<String, Invalid> {
+    let mut compacted = String::with_capacity(pattern.len());
+    let mut characters = pattern.char_indices().peekable();
+    let mut opened_classes = Vec::new();
+
+    while let Some((index, character)) = characters.next() {
+        match character {
+            '\\' => {
+                let Some((_, escaped)) = characters.next() else {
+                    let message = "Dangling `\\` at the end of the pattern";
+                    return Err(Invalid::new(index..index + 1, message));
+                };
+
+                compacted.push(character);
+                compacted.push(escaped);
+                continue;
+            },
+            '[' => {
+                opened_classes.push(index);
+                compacted.push(character);
+
+                // A `]` right after the opening `[` or `[^` is a literal
+                if let Some((_, '^')) = characters.peek() {
+                    compacted.push('^');
+                    characters.next();
+                }
+                if let Some((_, ']')) = characters.peek() {
+                    compacted.push(']');
+                    characters.next();
+                }
+                continue;
+            },
+            ']' if !opened_classes.is_empty() => {
+                opened_classes.pop();
+            },
+            _ if !opened_classes.is_empty() => (),
+            '#' => {
+                while characters.next_if(|&(_, c)| c != '\n').is_some() {}
+                continue;
+            },
+            character if character.is_whitespace() => continue,
+            _ => (),
+        }
+
+        compacted.push(character);
+    }
+
+    if let Some(index) = opened_classes.pop() {
+        let message = "Unclosed character class";
+        return Err(Invalid::new(index..index + 1, message));
+    }
+
+    Ok(compacted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compacted(pattern: &str) -> String {
+        compact(pattern)
+            .unwrap_or_else(|invalid| panic!("{}", invalid.message))
+    }
+
+    #[test]
+    fn removes_whitespace_and_comments() {
+        let pattern = r"
+            ^
+            (?P<year>  \d{4} )  # the year
+            -
+            (?P<month> \d{2} )  # the month
+            $
+        ";
+
+        assert_eq!(compacted(pattern), r"^(?P<year>\d{4})-(?P<month>\d{2})$");
+    }
+
+    #[test]
+    fn keeps_escapes_and_classes() {
+        assert_eq!(compacted(r"a\ b \# c [ #] d"), r"a\ b\#c[ #]d");
+        assert_eq!(compacted(r"[] #] x"), r"[] #]x");
+        assert_eq!(compacted(r"[^] #] x"), r"[^] #]x");
+        assert_eq!(compacted(r"[[:space:] #] x"), r"[[:space:] #]x");
+        assert_eq!(compacted(r"[\] #] x"), r"[\] #]x");
+        assert_eq!(compacted(r"[\[ #] x"), r"[\[ #]x");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(compact(r"a [b").err().unwrap().range, 2..3);
+        assert_eq!(compact(r"a [[]").err().unwrap().range, 3..4);
+        assert_eq!(compact(r"a \").err().unwrap().range, 2..3);
+    }
+}
@@ -0,0 +1,155 @@
+use super::bytes::Bytes;
+
+/// The marker a region starts with.
+const ANCHOR: &[u8] = b"ANCHOR:";
+/// The marker a region ends with.
+const ANCHOR_END: &[u8] = b"ANCHOR_END:";
+
+/// Returns the marker and the name of the region if `line` is a marker, e.g.
+/// `// ANCHOR: name` if `comment` is `//`.  The name is the first word after
+/// the marker, so that the rest of the line can close a block comment, e.g.
+/// `<!-- ANCHOR: name -->`.
+fn marker_of<'a>(
+    line: &'a [u8],
+    comment: &str,
+) -> Option<(&'static [u8], &'a [u8])> {
+    let rest = line.trim().strip_prefix(comment.as_bytes())?.trim();
+    let (marker, rest) = [ANCHOR, ANCHOR_END]
+        .into_iter()
+        .find_map(|marker| Some((marker, rest.strip_prefix(marker)?)))?;
+    let name = rest
+        .trim()
+        .split(u8::is_ascii_whitespace)
+        .next()
+        .unwrap_or_default();
+
+    Some((marker, name))
+}
+
+/// Returns the lines between the `ANCHOR: name` and the `ANCHOR_END: name`
+/// markers (which are prefixed by `comment`) of `content`, without the markers
+/// of other regions in it.
+pub fn region(
+    content: &[u8],
+    name: &str,
+    comment: &str,
+) -> Result<Vec<u8>, String> {
+    let mut lines = content.lines();
+    let is_start = |line: &[u8]| {
+        marker_of(line, comment) == Some((ANCHOR, name.as_bytes()))
+    };
+    if !lines.by_ref().any(is_start) {
+        return Err(format!(
+            "Region `{name}` is not found, expected a line of \
+             `{comment} ANCHOR: {name}`",
+        ));
+    }
+
+    let mut selected = Vec::new();
+    for line in lines {
+        match marker_of(line, comment) {
+            Some((ANCHOR_END, found)) if found == name.as_bytes() => {
+                return Ok(selected.join(&b'\n'));
+            },
+            Some(_) => (),
+            None => selected.push(line),
+        }
+    }
+
+    Err(format!(
+        "Region `{name}` is not closed, expected a line of \
+         `{comment} ANCHOR_END: {name}`",
+    ))
+}
+
+/// Returns the lines of `content` from the `first` to the `last` one (both
+/// inclusive and numbered from 1), or until the end if `last` is `None`.
+pub fn lines(
+    content: &[u8],
+    first: usize,
+    last: Option<usize>,
+) -> Result<Vec<u8>, String> {
+    let count = content.lines().count();
+    let last = last.unwrap_or(count);
+
+    if first == 0 {
+        return Err(String::from("Lines are numbered from 1"));
+    }
+    if first > last {
+        return Err(format!("Line range {first}..={last} is empty"));
+    }
+    if last > count {
+        return Err(format!(
+            "Line range {first}..={last} is out of bounds, as there are only \
+             {count} lines",
+        ));
+    }
+
+    let selected: Vec<_> =
+        content.lines().skip(first - 1).take(last + 1 - first).collect();
+
+    Ok(selected.join(&b'\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "\
+        -- ANCHOR: all\n\
+        SELECT *\n\
+        -- ANCHOR: users\r\n\
+        FROM users\n\
+        --ANCHOR_END:users\n\
+        -- ANCHOR_END: all\n\
+        <!-- ANCHOR: html -->\n\
+        <p></p>\n\
+        <!-- ANCHOR_END: html -->";
+
+    fn select(name: &str, comment: &str) -> Result<String, String> {
+        region(CONTENT.as_bytes(), name, comment)
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+    }
+
+    #[test]
+    fn regions() {
+        assert_eq!(select("users", "--").unwrap(), "FROM users");
+        assert_eq!(select("all", "--").unwrap(), "SELECT *\nFROM users");
+        assert_eq!(select("html", "<!--").unwrap(), "<p></p>");
+        assert_eq!(
+            select("user", "--").unwrap_err(),
+            "Region `user` is not found, expected a line of \
+             `-- ANCHOR: user`",
+        );
+        assert!(select("users", "//").is_err());
+        assert_eq!(
+            region(b"// ANCHOR: a\nb", "a", "//").unwrap_err(),
+            "Region `a` is not closed, expected a line of \
+             `// ANCHOR_END: a`",
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let select = |first, last| {
+            lines(b"a\nb\r\nc\nd", first, last)
+                .map(|bytes| String::from_utf8(bytes).unwrap())
+        };
+
+        assert_eq!(select(2, Some(3)).unwrap(), "b\nc");
+        assert_eq!(select(3, None).unwrap(), "c\nd");
+        assert_eq!(select(4, Some(4)).unwrap(), "d");
+        assert_eq!(
+            select(0, Some(1)).unwrap_err(),
+            "Lines are numbered from 1",
+        );
+        assert_eq!(
+            select(3, Some(2)).unwrap_err(),
+            "Line range 3..=2 is empty",
+        );
+        assert_eq!(
+            select(2, Some(5)).unwrap_err(),
+            "Line range 2..=5 is out of bounds, as there are only 4 lines",
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use std::str;
+
+pub struct Lines<'a> {
+    lines: str::Lines<'a>,
+}
+
+impl<'a> From<&'a str> for Lines<'a> {
+    fn from(string: &'a str) -> Self {
+        Self { lines: string.lines() }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                return Some(trimmed)
+            }
+        }
+    }
+}
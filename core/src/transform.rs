@@ -0,0 +1,661 @@
+use std::{
+    str::{self, Utf8Error},
+    path,
+    borrow::Cow,
+    ops::Range,
+};
+use super::{
+    hex,
+    pem,
+    regex,
+    graphql,
+    invisible,
+    string,
+    c_string,
+    byte_string,
+    base64::{self, Alphabet},
+    bytes::Bytes as _,
+    cfg::CfgLines,
+    constraint::Constraints,
+    invalid::Invalid,
+    lint::{Lint, content_lints},
+    preview::{preview, escaped},
+    vars::substituted,
+    trimmed_string_joined_with_delimiter,
+    trimmed_byte_string_joined_with_delimiter,
+    trimmed_c_string_joined_with_delimiter,
+};
+
+const NUL: &str = "Nul-terminated UTF-16 string cannot contain a nul character";
+
+/// The kind of literal the input is processed as.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Literal {
+    #[default]
+    String,
+    ByteString,
+    CString,
+}
+
+/// The same as `lang = ...` of `trim!`.
+#[derive(Clone, Copy)]
+pub enum Lang {
+    GraphQl,
+    Regex,
+}
+
+impl Lang {
+    pub fn minify(self, string: &str) -> Result<String, Invalid> {
+        match self {
+            Self::GraphQl => graphql::minify(string),
+            Self::Regex => regex::compact(string),
+        }
+    }
+}
+
+/// The same as `decode = ...` of `trim!`.
+#[derive(Clone)]
+pub enum Decode {
+    Hex,
+    Base64(Alphabet),
+    Pem(String),
+}
+
+impl Decode {
+    /// Decodes the already trimmed `lines` of `value`.
+    pub fn decode<'a>(
+        &self,
+        value: &'a [u8],
+        lines: impl Iterator<Item = &'a [u8]>,
+    ) -> Result<Vec<u8>, Invalid> {
+        match self {
+            Self::Hex => hex::decode(value, lines),
+            Self::Base64(alphabet) => base64::decode(value, lines, *alphabet),
+            Self::Pem(label) => pem::decode(value, lines, label),
+        }
+    }
+}
+
+/// The same as `join_with = ...` of `trim!`.
+#[derive(Clone)]
+pub enum Delimiter {
+    Bytes(Vec<u8>),
+    /// The path separator of the platform the transformation runs on
+    MainSeparator,
+}
+
+impl Delimiter {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Bytes(bytes) => bytes.clone(),
+            Self::MainSeparator => path::MAIN_SEPARATOR_STR.into(),
+        }
+    }
+}
+
+impl From<&str> for Delimiter {
+    fn from(string: &str) -> Self {
+        Self::Bytes(string.into())
+    }
+}
+
+impl From<char> for Delimiter {
+    fn from(character: char) -> Self {
+        Self::Bytes(character.to_string().into_bytes())
+    }
+}
+
+/// The same as `as = ...` of `trim!`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    String,
+    CString,
+    ByteString,
+    OsString,
+    Path,
+}
+
+/// Describes the invalid UTF-8 sequence of `bytes` found by `error`.
+fn invalid_utf8(bytes: &[u8], error: Utf8Error) -> Invalid {
+    let start = error.valid_up_to();
+    let end = error.error_len().map_or(bytes.len(), |length| start + length);
+    Invalid::new(start..end, "String cannot contain invalid UTF-8")
+}
+
+impl Output {
+    /// Checks whether `bytes` can be part of the output.
+    pub fn check(self, bytes: &[u8]) -> Result<(), Invalid> {
+        match self {
+            Self::String
+            | Self::OsString
+            | Self::Path => match str::from_utf8(bytes) {
+                Ok(_) => Ok(()),
+                Err(error) => Err(invalid_utf8(bytes, error)),
+            },
+            Self::CString => match bytes.iter().position(|&byte| byte == 0) {
+                Some(index) => Err(Invalid::new(
+                    index..index + 1,
+                    "C-string cannot contain a nul character",
+                )),
+                None => Ok(()),
+            },
+            Self::ByteString => Ok(()),
+        }
+    }
+}
+
+/// The same as `encode = ...` of `trim!`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Encode {
+    Utf16,
+    Utf16Nul,
+    Utf32,
+}
+
+impl Encode {
+    /// Checks whether `bytes` can be part of the string to be encoded, i.e.
+    /// that they do not contain a nul character if it should be nul-terminated.
+    pub fn check(self, bytes: &[u8]) -> Result<(), Invalid> {
+        match (self, bytes.iter().position(|&byte| byte == 0)) {
+            (Self::Utf16Nul, Some(index)) => {
+                Err(Invalid::new(index..index + 1, NUL))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the code units of `string` (including the nul-terminator, if
+    /// any), which fit in a `u16`, unless it is encoded as UTF-32.
+    pub fn units(self, string: &str) -> Vec<u32> {
+        match self {
+            Self::Utf16 => string.encode_utf16().map(u32::from).collect(),
+            Self::Utf16Nul => {
+                string.encode_utf16().chain([0]).map(u32::from).collect()
+            },
+            Self::Utf32 => string.chars().map(u32::from).collect(),
+        }
+    }
+}
+
+/// The names of the options (i.e. the named parameters of `trim!`), which
+/// cannot be combined, where the second one of a pair is the one reported.
+const EXCLUSIVE: [(&str, &str); 6] = [
+    ("lang", "join_with"),
+    ("decode", "join_with"),
+    ("decode", "lang"),
+    ("decode", "encode"),
+    ("encode", "as"),
+    ("decode", "vars"),
+];
+
+/// The options which can only be used with some kinds of literals.
+const RESTRICTED: [(&str, &[Literal], &str); 5] = [
+    ("lang", &[Literal::String], "string literals"),
+    ("encode", &[Literal::String], "string literals"),
+    ("vars", &[Literal::String], "string literals"),
+    (
+        "decode",
+        &[Literal::String, Literal::ByteString],
+        "string and byte-string literals",
+    ),
+    (
+        "require_utf8",
+        &[Literal::ByteString, Literal::CString],
+        "byte-string and C-string literals",
+    ),
+];
+
+/// An option (i.e. a named parameter of `trim!`) which cannot be used.
+pub enum Conflict {
+    /// The option `name` cannot be combined with `other`
+    Exclusive { name: &'static str, other: &'static str },
+    /// The option `name` can only be used with the `literals`
+    Restricted { name: &'static str, literals: &'static str },
+}
+
+impl Conflict {
+    /// Returns the name of the option which cannot be used.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Exclusive { name, .. } | Self::Restricted { name, .. } => {
+                name
+            },
+        }
+    }
+
+    /// Describes the conflict, where `quoted` returns how an option is
+    /// referred to by its name.
+    pub fn describe(&self, quoted: impl Fn(&str) -> String) -> String {
+        match self {
+            Self::Exclusive { name, other } => format!(
+                "{} cannot be combined with {}",
+                quoted(name),
+                quoted(other),
+            ),
+            Self::Restricted { name, literals } => {
+                format!("{} can only be used with {literals}", quoted(name))
+            },
+        }
+    }
+}
+
+/// Returns the conflicts among the options (for which `is_set` holds) for
+/// the kind of `literal`, which are the same for `trim!` and for the
+/// transformations alike.
+pub fn conflicts(
+    literal: Literal,
+    is_set: impl Fn(&str) -> bool,
+) -> Vec<Conflict> {
+    let exclusive = EXCLUSIVE
+        .into_iter()
+        .filter(|(other, name)| is_set(name) && is_set(other))
+        .map(|(other, name)| Conflict::Exclusive { name, other });
+    let restricted = RESTRICTED
+        .into_iter()
+        .filter(|(name, literals, _)| {
+            is_set(name) && !literals.contains(&literal)
+        })
+        .map(|(name, _, literals)| Conflict::Restricted { name, literals });
+
+    exclusive.chain(restricted).collect()
+}
+
+/// Describes why the transformations cannot be applied to an input.
+pub enum Error {
+    /// The options cannot be combined
+    Conflict(Conflict),
+    /// The value of the option called `name` is invalid
+    Option { name: &'static str, message: String },
+    /// A part of the input is invalid
+    Input(Invalid),
+    /// The output is invalid, or does not meet the constraints
+    Output(Vec<String>),
+}
+
+impl Error {
+    /// Describes the error, where `input` is the input the transformations
+    /// have been applied to, and `quoted` returns how an option is referred
+    /// to by its name.
+    pub fn describe(
+        &self,
+        input: &[u8],
+        quoted: impl Fn(&str) -> String,
+    ) -> String {
+        match self {
+            Self::Conflict(conflict) => conflict.describe(quoted),
+            Self::Option { message, .. } => message.clone(),
+            Self::Input(invalid) => invalid.located(input),
+            Self::Output(messages) => messages.join("\n"),
+        }
+    }
+}
+
+/// The transformations `trim!` would apply to a literal, which can be applied
+/// to any input, e.g. to the content of a file.
+#[derive(Clone, Default)]
+pub struct Options {
+    pub literal: Literal,
+    pub join_with: Option<Delimiter>,
+    pub lang: Option<Lang>,
+    pub decode: Option<Decode>,
+    /// The encoding of the output, whose code units are produced in
+    /// little-endian byte order
+    pub encode: Option<Encode>,
+    pub output: Option<Output>,
+    pub constraints: Constraints,
+    /// The names and the values of the variables, if the placeholders should
+    /// be substituted
+    pub vars: Option<Vec<(String, String)>>,
+    /// The `cfg` predicates which hold, if the lines can be marked with
+    /// `@cfg(...)`
+    pub cfg_lines: Option<Vec<String>>,
+    pub allow_invisible: bool,
+}
+
+impl Options {
+    /// Returns whether the option called `name` (i.e. the same as the named
+    /// parameter of `trim!`) is set.
+    fn is_set(&self, name: &str) -> bool {
+        match name {
+            "join_with" => self.join_with.is_some(),
+            "lang" => self.lang.is_some(),
+            "decode" => self.decode.is_some(),
+            "encode" => self.encode.is_some(),
+            "as" => self.output.is_some(),
+            "vars" => self.vars.is_some(),
+            "require_utf8" => self.constraints.require_utf8,
+            _ => false,
+        }
+    }
+
+    /// Returns the kind of output which is the same as the kind of literal.
+    fn literal_output(&self) -> Output {
+        match self.literal {
+            Literal::String => Output::String,
+            Literal::ByteString => Output::ByteString,
+            Literal::CString => Output::CString,
+        }
+    }
+
+    /// Returns the kind of the output, which is the same as the kind of the
+    /// literal, or a byte-string if it is decoded, unless it is set.
+    pub fn output(&self) -> Output {
+        match (self.output, &self.decode) {
+            (Some(output), _) => output,
+            (None, Some(_)) => Output::ByteString,
+            (None, None) => self.literal_output(),
+        }
+    }
+
+    /// Returns the number of non-blank lines of `input`.
+    fn lines_of(&self, input: &[u8]) -> usize {
+        match (self.literal, self.string_of(input)) {
+            (_, Ok(Some(string))) => string::Lines::from(string).count(),
+            (Literal::CString, _) => c_string::Lines::from(input).count(),
+            _ => byte_string::Lines::from(input).count(),
+        }
+    }
+
+    /// Returns the problems of the options, i.e. the combinations of them
+    /// which `trim!` would reject, and the invalid delimiters.
+    pub fn errors(&self) -> Vec<Error> {
+        let mut errors: Vec<_> =
+            conflicts(self.literal, |name| self.is_set(name))
+                .into_iter()
+                .map(Error::Conflict)
+                .collect();
+
+        let invalid = |message: &str| Error::Option {
+            name: "join_with",
+            message: String::from(message),
+        };
+        let delimiter = match (self.literal, &self.join_with) {
+            (_, None) => return errors,
+            (
+                Literal::ByteString | Literal::CString,
+                Some(Delimiter::MainSeparator),
+            ) => {
+                errors.push(invalid(
+                    "`MAIN_SEPARATOR` can only be used with strings",
+                ));
+                return errors;
+            },
+            (_, Some(delimiter)) => delimiter.to_bytes(),
+        };
+
+        match self.literal {
+            Literal::String if str::from_utf8(&delimiter).is_err() => {
+                errors.push(invalid(
+                    "Delimiter for strings must be valid UTF-8",
+                ));
+            },
+            Literal::CString if delimiter.contains(&b'\0') => {
+                errors.push(invalid(
+                    "Delimiter for C-string cannot be the nul-terminator",
+                ));
+            },
+            _ => (),
+        }
+
+        let checked = match self.output {
+            Some(output) => output.check(&delimiter),
+            None => Ok(()),
+        };
+        let checked = checked.and_then(|()| match self.allow_invisible {
+            true => Ok(()),
+            false => invisible::check(&delimiter),
+        });
+        if let Err(invalid) = checked {
+            errors.push(Error::Option {
+                name: "join_with",
+                message: format!("Invalid delimiter: {}", invalid.message),
+            });
+        }
+
+        errors
+    }
+
+    /// Checks the combination of the options the same way `trim!` checks its
+    /// named parameters.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.errors().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `input` as a string if it is processed as a string literal, or
+    /// describes why it cannot be the value of the literal.
+    fn string_of<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> Result<Option<&'a str>, Invalid> {
+        match self.literal {
+            Literal::String => str::from_utf8(input)
+                .map(Some)
+                .map_err(|error| invalid_utf8(input, error)),
+            Literal::CString => Output::CString.check(input).map(|()| None),
+            Literal::ByteString => Ok(None),
+        }
+    }
+
+    /// Returns the lines of `input` (if `cfg_lines` is set) whose predicates
+    /// hold, without their markers.
+    pub fn selected<'a>(
+        &self,
+        input: &'a [u8],
+    ) -> Result<Cow<'a, [u8]>, Error> {
+        let Some(holding) = &self.cfg_lines else {
+            return Ok(Cow::Borrowed(input));
+        };
+
+        let lines = CfgLines::new(input).map_err(Error::Input)?;
+        Ok(Cow::Owned(lines.selected(holding)))
+    }
+
+    /// Applies the transformations to `input` (i.e. to the lines already
+    /// selected by [`Options::selected`]), and returns the output before it is
+    /// encoded by [`Options::encoded`].
+    pub fn transformed(&self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        self.check()?;
+
+        if !self.allow_invisible {
+            invisible::check(input).map_err(Error::Input)?;
+        }
+        let string = self.string_of(input).map_err(Error::Input)?;
+        let lines = self.lines_of(input);
+
+        let output = self.output();
+        let bytes = match (&self.decode, string) {
+            (Some(decode), Some(string)) => {
+                let lines = string::Lines::from(string).map(str::as_bytes);
+                decode.decode(input, lines).map_err(Error::Input)?
+            },
+            (Some(decode), None) => {
+                let lines = byte_string::Lines::from(input);
+                decode.decode(input, lines).map_err(Error::Input)?
+            },
+            (None, _) => self.joined(input, string)?,
+        };
+
+        let message = match &self.decode {
+            Some(_) => "Invalid decoded bytes",
+            None => "Invalid trimmed literal",
+        };
+        output.check(&bytes).map_err(|invalid| {
+            Error::Output(vec![format!("{message}: {}", invalid.message)])
+        })?;
+        if self.encode == Some(Encode::Utf16Nul) && bytes.contains(&b'\0') {
+            return Err(Error::Output(vec![String::from(NUL)]));
+        }
+
+        let messages = self.constraints.check(&bytes, lines);
+        if !messages.is_empty() {
+            return Err(Error::Output(messages));
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns the trimmed lines of `input` (which is `string`, if it is
+    /// processed as a string) joined together, or minified as `lang`, with the
+    /// placeholders substituted if `vars` is set.
+    fn joined(
+        &self,
+        input: &[u8],
+        string: Option<&str>,
+    ) -> Result<Vec<u8>, Error> {
+        if self.output.is_some() {
+            self.output().check(input).map_err(Error::Input)?;
+        }
+        if let Some(encode) = self.encode {
+            encode.check(input).map_err(Error::Input)?;
+        }
+
+        let delimiter = self
+            .join_with
+            .as_ref()
+            .map_or_else(Vec::new, Delimiter::to_bytes);
+        let Some(string) = string else {
+            let bytes = match self.literal {
+                Literal::CString => {
+                    trimmed_c_string_joined_with_delimiter(input, &delimiter)
+                },
+                _ => trimmed_byte_string_joined_with_delimiter(
+                    input,
+                    &delimiter,
+                ),
+            };
+            return Ok(bytes);
+        };
+
+        let string = match self.lang {
+            Some(lang) => lang.minify(string).map_err(Error::Input)?,
+            None => {
+                // NOTE: The delimiter has been checked to be valid UTF-8
+                let delimiter = String::from_utf8_lossy(&delimiter);
+                trimmed_string_joined_with_delimiter(string, &delimiter)
+            },
+        };
+
+        let Some(vars) = &self.vars else {
+            return Ok(string.into_bytes());
+        };
+        let string = substituted(&string, |name| {
+            vars.iter()
+                .find(|(variable, _)| variable == name)
+                .map(|(_, value)| value.as_str())
+        })
+        .map_err(|message| Error::Output(vec![message]))?;
+
+        // NOTE: As the values are not part of the input, the result is checked
+        //       the same way the input is
+        let checked = self.output().check(string.as_bytes());
+        let checked = checked.and_then(|()| match self.allow_invisible {
+            true => Ok(()),
+            false => invisible::check(string.as_bytes()),
+        });
+        checked.map_err(|invalid| Error::Option {
+            name: "vars",
+            message: format!(
+                "Invalid value substituted by `vars`: {}",
+                invalid.located(string.as_bytes()),
+            ),
+        })?;
+
+        Ok(string.into_bytes())
+    }
+
+    /// Returns the `output` (produced by [`Options::transformed`]) encoded as
+    /// `encode` (if it is set).
+    pub fn encoded(&self, output: Vec<u8>) -> Vec<u8> {
+        let Some(encode) = self.encode else {
+            return output;
+        };
+
+        // NOTE: Only strings can be encoded, which remain valid UTF-8
+        let string = String::from_utf8_lossy(&output);
+        let units = encode.units(&string);
+        match encode {
+            Encode::Utf32 => {
+                units.iter().flat_map(|unit| unit.to_le_bytes()).collect()
+            },
+            Encode::Utf16 | Encode::Utf16Nul => units
+                .iter()
+                .flat_map(|&unit| (unit as u16).to_le_bytes())
+                .collect(),
+        }
+    }
+
+    /// Applies all the transformations to `input`, and returns the output, or
+    /// the description of the first error.
+    pub fn trimmed(&self, input: &[u8]) -> Result<Vec<u8>, String> {
+        let describe = |error: Error, input: &[u8]| {
+            error.describe(input, |name| format!("`{name}`"))
+        };
+
+        let selected = self
+            .selected(input)
+            .map_err(|error| describe(error, input))?;
+        let output = self
+            .transformed(&selected)
+            .map_err(|error| describe(error, &selected))?;
+
+        Ok(self.encoded(output))
+    }
+
+    /// Returns the lints of the transformation of `input` to `output` (which
+    /// is produced by [`Options::transformed`]), each with the byte range of
+    /// the suspicious part of the input, if it is about a part of it.
+    pub fn lints(
+        &self,
+        input: &[u8],
+        output: &[u8],
+    ) -> Vec<(Lint, Option<Range<usize>>)> {
+        let mut lints: Vec<_> = content_lints(input)
+            .into_iter()
+            .map(|(lint, range)| (lint, Some(range)))
+            .collect();
+
+        if self.join_with.is_some() && self.lines_of(input) <= 1 {
+            lints.push((Lint::NeedlessJoinWith, None));
+        }
+
+        // NOTE: A single-line literal is most likely passed to `trim!` on
+        //       purpose, e.g. for consistency with the ones around it
+        let is_same_kind = self.decode.is_none()
+            && self.encode.is_none()
+            && self.output() == self.literal_output();
+        let is_unchanged = input.contains(&b'\n') && input == output;
+        if is_same_kind && is_unchanged {
+            lints.push((Lint::Unchanged, None));
+        }
+
+        lints
+    }
+
+    /// Describes how the lines of `input` are trimmed, and what the `output`
+    /// (produced by [`Options::transformed`]) is, the same way as `debug` of
+    /// `trim!` does.
+    pub fn preview(&self, input: &[u8], output: &[u8]) -> String {
+        let marker = match (&self.lang, &self.decode, &self.join_with) {
+            (Some(_), _, _) | (_, Some(_), _) => None,
+            (_, _, Some(Delimiter::MainSeparator)) => {
+                Some(String::from("MAIN_SEPARATOR"))
+            },
+            (_, _, join_with) => Some(escaped(
+                &join_with.as_ref().map_or_else(Vec::new, Delimiter::to_bytes),
+            )),
+        };
+
+        let lines: Box<dyn Iterator<Item = (&[u8], &[u8])>> =
+            match self.string_of(input) {
+                Ok(Some(string)) => Box::new(string.lines().map(|line| {
+                    (line.as_bytes(), line.trim().as_bytes())
+                })),
+                _ => Box::new(input.lines().map(|line| (line, line.trim()))),
+            };
+
+        preview(lines, marker.as_deref(), output)
+    }
+}
@@ -0,0 +1,82 @@
+/// Substitutes the `{name}` placeholders of `string` with the values returned
+/// by `value_of`, and unescapes `{{` and `}}`.
+pub fn substituted<'a>(
+    string: &str,
+    value_of: impl Fn(&str) -> Option<&'a str>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(string.len());
+    let mut rest = string;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        result.push_str(&rest[..index]);
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+
+        if let Some(after) = rest.strip_prefix(brace) {
+            result.push_str(brace);
+            rest = after;
+            continue;
+        }
+
+        if brace == "}" {
+            return Err(String::from(
+                "Unmatched `}` in the trimmed string, which has to be escaped \
+                 as `}}`",
+            ));
+        }
+
+        let Some(end) = rest.find('}') else {
+            return Err(String::from(
+                "Unclosed `{` in the trimmed string, which has to be escaped \
+                 as `{{`",
+            ));
+        };
+        let name = &rest[..end];
+        let value = value_of(name).ok_or_else(|| {
+            format!("Unknown placeholder `{{{name}}}` in the trimmed string")
+        })?;
+        result.push_str(value);
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn substitute(string: &str) -> Result<String, String> {
+        substituted(string, |name| match name {
+            "name" => Some("strim"),
+            "version" => Some("{0.6.0}"),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(
+            substitute("{name} {version}").unwrap(),
+            "strim {0.6.0}",
+        );
+        assert_eq!(substitute("{{name}} {{}}").unwrap(), "{name} {}");
+        assert_eq!(substitute("{{{name}}}").unwrap(), "{strim}");
+        assert_eq!(substitute("no placeholders").unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn invalid_placeholders() {
+        assert_eq!(
+            substitute("{other}").unwrap_err(),
+            "Unknown placeholder `{other}` in the trimmed string",
+        );
+        assert_eq!(
+            substitute("{}").unwrap_err(),
+            "Unknown placeholder `{}` in the trimmed string",
+        );
+        assert!(substitute("{name").is_err());
+        assert!(substitute("name}").is_err());
+    }
+}
@@ -0,0 +1,4 @@
+
+    SELECT name
+    FROM users
+    WHERE id = ?
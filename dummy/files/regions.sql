@@ -0,0 +1,7 @@
+-- ANCHOR: all
+SELECT name
+-- ANCHOR: from
+FROM users
+-- ANCHOR_END: from
+WHERE id = ?
+-- ANCHOR_END: all
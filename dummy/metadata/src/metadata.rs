@@ -0,0 +1,42 @@
+// NOTE: The manifest of this crate forbids `¤` by default, which overrides the
+//       `$` forbidden by the workspace, whose `max_len = 8` applies as well
+use strim::{define_trim, trim};
+
+define_trim!(price_trim, forbid = "$");
+
+#[test]
+fn valid() {
+    assert_eq!(trim!(" a \n b ", join_with = ' '), "a b");
+    assert_eq!(
+        trim!("
+            10 $
+        "),
+        "10 $",
+    );
+    assert_eq!(
+        trim!("
+            10 ¤
+        ", forbid = "$"),
+        "10 ¤",
+    );
+    assert_eq!(trim!(b" \xa4 ", forbid = "$"), b"\xa4");
+    assert_eq!(price_trim!(" 10 ¤ "), "10 ¤");
+    assert_eq!(trim!("0123456789", max_len = 10), "0123456789");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid() {
+    trim!("10 ¤");
+    trim!("
+        10
+        ¤
+    ", join_with = ' ');
+    trim!("012345678");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_byte_string() {
+    trim!(b"10 \xc2\xa4");
+}
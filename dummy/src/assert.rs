@@ -0,0 +1,56 @@
+use strim::assert_trimmed_eq;
+
+#[test]
+fn valid() {
+    let rendered = String::from("SELECT name\nFROM users");
+    assert_trimmed_eq!(rendered, r#"
+        SELECT name
+        FROM users
+    "#, join_with = '\n');
+    assert_trimmed_eq!("a b", "
+        a
+        b
+    ", join_with = " ");
+    assert_trimmed_eq!("a\nb", "a\nb", join_with = '\n');
+}
+
+#[test]
+fn valid_trim_actual() {
+    let rendered = "
+        query {
+            user(id: 1) { name }
+        }
+    ";
+    assert_trimmed_eq!(rendered, "
+        query {
+          user(id: 1) {
+            name
+          }
+        }
+    ", lang = graphql, trim_actual);
+    assert_trimmed_eq!(" \ta\r\n  b ", " ab ", trim_actual = true);
+}
+
+#[test]
+#[should_panic(expected = "  a\n- b\n+ b\u{b7}\n  c")]
+fn mismatch() {
+    assert_trimmed_eq!("a\nb \nc", "
+        a
+        b
+        c
+    ", join_with = '\n');
+}
+
+#[test]
+#[should_panic(expected = "Cannot trim the actual value")]
+fn invalid_actual() {
+    assert_trimmed_eq!("a\u{200b}", "a", trim_actual);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid() {
+    assert_trimmed_eq!(b"a", b"a");
+    assert_trimmed_eq!("a", "a", encode = utf16);
+    assert_trimmed_eq!("a", " a ", trim_actual, trim_actual = false);
+}
@@ -0,0 +1,72 @@
+use std::ffi::CStr;
+use strim::trim;
+
+const HINTS: &str = trim!("
+    SELECT *
+    FROM users
+    @cfg(feature = \"compile-errors\") WHERE invalid
+    @cfg(test) LIMIT 1
+", join_with = ' ', cfg_lines);
+
+const NESTED: &[u8] = trim!(br#"
+    a
+    @cfg(all(test, not(feature = "compile-errors"))) b
+    @cfg(any(unix, windows)) c
+    @cfg(test) d
+"#, join_with = b',', cfg_lines = true);
+
+const SELECTED: &CStr = trim!(c"
+    @cfg(test) a
+    @cfg(not(test)) b
+", cfg_lines);
+
+#[test]
+fn valid() {
+    assert_eq!(HINTS, "SELECT * FROM users LIMIT 1");
+    assert_eq!(
+        NESTED,
+        if cfg!(any(unix, windows)) { b"a,b,c,d".as_slice() } else { b"a,b,d" },
+    );
+    assert_eq!(SELECTED, c"a");
+}
+
+#[test]
+fn valid_without_cfg_lines() {
+    assert_eq!(
+        trim!("
+            a
+            @cfg(test) b
+        ", join_with = ' '),
+        "a @cfg(test) b",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_markers() {
+    trim!("@cfg(any(test)", cfg_lines);
+    trim!("@cfg(1 + 2) a", cfg_lines);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_predicates() {
+    trim!("
+        @cfg(a) a
+        @cfg(b) b
+        @cfg(c) c
+        @cfg(d) d
+        @cfg(e) e
+        @cfg(f) f
+        @cfg(g) g
+    ", cfg_lines);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_variant() {
+    trim!(c"
+        a
+        @cfg(test) b
+    ", cfg_lines, max_len = 1);
+}
@@ -14,3 +14,26 @@ fn no_arguments() {
 fn too_many_arguments() {
     trim!("hello" "world");
 }
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn misspelled_named_parameters() {
+    trim!("hello", join_wiht = ' ');
+    trim!("hello", lnag = graphql);
+    trim!("hello", ecnode = utf16);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn duplicated_named_parameters() {
+    trim!("hello", join_with = ' ', join_with = '\n');
+    trim!(b"hello", as = str, as = bytes, as = c_str);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn all_errors_at_once() {
+    trim!("hello" "world", join_wiht = ' ', lang = python, as = string);
+    trim!(c"hello", lang = graphql, encode = utf16, decode = hex);
+    trim!("hello", join_with = "", lang = regex, decode = hex, as = str);
+}
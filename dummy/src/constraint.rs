@@ -0,0 +1,101 @@
+use strim::trim;
+
+#[test]
+fn valid() {
+    assert_eq!(
+        trim!("
+            Hello,
+            World!
+        ", join_with = ' ', max_len = 13, min_lines = 2, ascii_only),
+        "Hello, World!",
+    );
+    assert_eq!(
+        trim!(b"
+            Hello,
+            W\xc3\xb6rld!
+        ", join_with = b' ', require_utf8, forbid = "\r\n\0"),
+        b"Hello, W\xc3\xb6rld!",
+    );
+    assert_eq!(
+        trim!(c"
+            Hello,
+            World!
+        ", join_with = b' ', max_len = 13, ascii_only = true),
+        c"Hello, World!",
+    );
+    assert_eq!(
+        trim!("\u{f6}", ascii_only = false),
+        "\u{f6}",
+    );
+    assert_eq!(trim!("48 69", decode = hex, max_len = 2), b"Hi");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_max_len() {
+    trim!("
+        Hello,
+        World!
+    ", join_with = ' ', max_len = 12);
+    trim!(c"Hello", max_len = 4);
+    trim!("48 69", decode = hex, max_len = 1);
+    trim!("hello", max_len = -1);
+    trim!("hello", max_len = "5");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_min_lines() {
+    trim!("
+        Hello, World!
+    ", min_lines = 2);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_ascii_only() {
+    trim!("
+        Hello,
+        W\u{f6}rld!
+    ", ascii_only);
+    trim!("hello\nworld", join_with = '\u{b7}', ascii_only);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_forbid() {
+    trim!("
+        SELECT *
+        FROM users; DROP TABLE users
+    ", join_with = ' ', forbid = ";'");
+    trim!("hello", forbid = "");
+    trim!("hello", forbid = 'x');
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_require_utf8() {
+    trim!(b"
+        hello
+        wor\xffld
+    ", require_utf8);
+    trim!("hello", require_utf8);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_all_at_once() {
+    trim!("
+        Hello,
+        W\u{f6}rld!
+    ", max_len = 4, min_lines = 3, ascii_only, forbid = "!");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_main_separator() {
+    trim!("
+        usr
+        local
+    ", join_with = MAIN_SEPARATOR, max_len = 4, forbid = "/\\");
+}
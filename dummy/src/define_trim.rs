@@ -0,0 +1,61 @@
+use strim::define_trim;
+
+define_trim!(sql_trim, join_with = ' ', max_len = 64);
+define_trim!(
+    /// Trims help texts.
+    #[allow(unused_macros)]
+    help_trim,
+    join_with = '\n',
+    ascii_only,
+);
+define_trim!(plain_trim);
+
+const USERS: &str = sql_trim!("
+    SELECT name
+    FROM users
+");
+
+#[test]
+fn valid() {
+    assert_eq!(USERS, "SELECT name FROM users");
+    assert_eq!(sql_trim!("SELECT 1"), "SELECT 1");
+    assert_eq!(
+        help_trim!("
+            Usage: strim [OPTIONS]
+            Options:
+        "),
+        "Usage: strim [OPTIONS]\nOptions:",
+    );
+    assert_eq!(plain_trim!(" a \n b "), "ab");
+}
+
+#[test]
+fn valid_override() {
+    assert_eq!(
+        sql_trim!("
+            SELECT name
+            FROM users
+        ", join_with = '\n'),
+        "SELECT name\nFROM users",
+    );
+    assert_eq!(help_trim!(b" a \n b ", join_with = b' '), b"a b");
+    assert_eq!(
+        help_trim!("
+            \u{e9}
+        ", ascii_only = false),
+        "\u{e9}",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_preset() {
+    sql_trim!(b"a\nb");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_override() {
+    sql_trim!("a\nb", lang = graphql);
+    help_trim!("\u{e9}");
+}
@@ -0,0 +1,78 @@
+use strim::{trim, trim_utf16};
+
+#[test]
+fn valid_utf16() {
+    const EXPECTED: &[u16] = &[
+        0x0048, 0x00e9, 0x006c, 0x006c, 0x00f6, 0x002c,
+        0x0020, 0xd83c, 0xdf0d, 0x0021,
+    ];
+
+    let actual: &'static [u16; 10] = trim!("
+        Héllö,
+        \u{1f30d}!
+    ", join_with = ' ', encode = utf16);
+    assert_eq!(actual, EXPECTED);
+
+    let actual: &'static [u16; 10] = trim_utf16!("
+        Héllö,
+        \u{1f30d}!
+    ", join_with = ' ');
+    assert_eq!(actual, EXPECTED);
+
+    let actual: &'static [u16; 0] = trim_utf16!("   ");
+    assert_eq!(actual, &[]);
+}
+
+#[test]
+fn valid_utf16_nul() {
+    let actual: &'static [u16; 4] = trim!("
+        a
+        b
+        c
+    ", encode = utf16_nul);
+    assert_eq!(actual, &[0x61, 0x62, 0x63, 0]);
+
+    let actual: &'static [u16; 1] = trim_utf16!("", encode = utf16_nul,);
+    assert_eq!(actual, &[0]);
+}
+
+#[test]
+fn valid_utf32() {
+    let actual: &'static [u32; 3] = trim!("
+        é
+        \u{1f30d}
+    ", join_with = ' ', encode = utf32);
+    assert_eq!(actual, &[0xe9, 0x20, 0x1f30d]);
+}
+
+#[test]
+fn valid_with_lang() {
+    let actual: &'static [u16; 5] = trim!("
+        { a, b }
+    ", lang = graphql, encode = utf16);
+    assert_eq!(actual, &[0x7b, 0x61, 0x20, 0x62, 0x7d]);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_encode() {
+    trim!("a", encode = utf8);
+    trim!("a", encode = "utf16");
+    trim!(b"a", encode = utf16);
+    trim!(c"a", encode = utf32);
+    trim!("00", decode = hex, encode = utf16);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_utf16_nul() {
+    trim!("a\0b", encode = utf16_nul);
+    trim!("a\nb", join_with = '\0', encode = utf16_nul);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_trim_utf16() {
+    trim_utf16!(b"a");
+    trim_utf16!(c"a");
+}
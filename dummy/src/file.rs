@@ -0,0 +1,106 @@
+use strim::{trim_file, trim_dir};
+
+const USERS: &str = trim_file!("files/queries/users.sql", join_with = ' ');
+const USER: &str = trim_file!("files/user.graphql", lang = graphql);
+static QUERIES: &[(&str, &str)] =
+    trim_dir!("files/queries", ext = "sql", join_with = ' ');
+
+#[test]
+fn valid_file() {
+    assert_eq!(USERS, "SELECT name FROM users WHERE id = ?");
+    assert_eq!(USER, "query{user(id:1){name}}");
+    assert_eq!(
+        trim_file!("files/invisible.txt", allow_invisible),
+        "a\u{200b}b",
+    );
+}
+
+#[test]
+fn valid_selection() {
+    assert_eq!(
+        trim_file!("files/regions.sql", region = "all", comment = "--"),
+        "SELECT nameFROM usersWHERE id = ?",
+    );
+    assert_eq!(
+        trim_file!("files/regions.sql", region = "from", comment = "--"),
+        "FROM users",
+    );
+    assert_eq!(
+        trim_file!("files/region.html", region = "body", comment = "<!--"),
+        "<body><p>Hello!</p></body>",
+    );
+    assert_eq!(
+        trim_file!("files/regions.sql", lines = 2..=4, join_with = ' '),
+        "SELECT name -- ANCHOR: from FROM users",
+    );
+    assert_eq!(trim_file!("files/regions.sql", lines = 6..7), "WHERE id = ?");
+    assert_eq!(
+        trim_file!("files/region.html", lines = 7..),
+        "</html>",
+    );
+}
+
+#[test]
+fn valid_dir() {
+    assert_eq!(
+        QUERIES,
+        [
+            ("nested/posts.sql", "SELECT * FROM posts"),
+            ("users.sql", "SELECT name FROM users WHERE id = ?"),
+        ],
+    );
+    assert_eq!(
+        trim_dir!("files/queries"),
+        [
+            ("README.txt", "Not a query"),
+            ("nested/posts.sql", "SELECT *FROM posts"),
+            ("users.sql", "SELECT nameFROM usersWHERE id = ?"),
+        ],
+    );
+    assert!(trim_dir!("files/queries", ext = "md").is_empty());
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_parameters() {
+    trim_file!(
+        "files/user.graphql",
+        lang = graphql,
+        join_with = ' ',
+        ext = "",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_missing() {
+    trim_file!("files/missing.txt");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_invisible() {
+    trim_dir!("files");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_region() {
+    trim_file!("files/regions.sql", region = "where", comment = "--");
+    trim_file!("files/regions.sql", region = "all");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_lines() {
+    trim_file!("files/regions.sql", lines = 0..=1);
+    trim_file!("files/regions.sql", lines = 3..3);
+    trim_file!("files/regions.sql", lines = 5..=8);
+    trim_file!("files/regions.sql", lines = 1..=2, region = "all");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_selection() {
+    trim_dir!("files/queries", comment = "--");
+}
@@ -0,0 +1,77 @@
+use strim::trim;
+
+#[test]
+fn valid_allowed() {
+    assert_eq!(
+        trim!("
+            hello\u{200b}
+            world
+        ", allow_invisible = true),
+        "hello\u{200b}world",
+    );
+    assert_eq!(
+        trim!(b"
+            hello\xe2\x80\xae
+            world
+        ", allow_invisible = true),
+        b"hello\xe2\x80\xaeworld",
+    );
+    assert_eq!(
+        trim!("
+            hello
+            world
+        ", allow_invisible = false),
+        "helloworld",
+    );
+    assert_eq!(
+        trim!("hello\u{200b}world", allow_invisible),
+        "hello\u{200b}world",
+    );
+    assert_eq!(
+        trim!("
+            hello
+            world
+        ", join_with = '\u{200b}', allow_invisible),
+        "hello\u{200b}world",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_invisible() {
+    trim!("
+        hello
+        wor\u{200b}ld
+    ");
+    trim!("SELECT * FROM users -- \u{202e}", join_with = ' ');
+    trim!(b"
+        hello
+        \xe2\x81\xa6world
+    ");
+    trim!(c"hello\u{ad}world");
+    trim!("
+        48 65 6c 6c 6f\u{feff}
+    ", decode = hex);
+    trim!("rm -rf /tmp/cache\u{e0020}\u{e002f}");
+    trim!("\u{2764}\u{fe0f}");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_invisible_delimiter() {
+    trim!("
+        hello
+        world
+    ", join_with = '\u{200b}');
+    trim!(b"
+        hello
+        world
+    ", join_with = b"\xe2\x80\x8b");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_allow_invisible() {
+    trim!("hello", allow_invisible = 1);
+    trim!("hello", allow_invisible = true, allow_invisible = true);
+}
@@ -0,0 +1,103 @@
+use strim::trim;
+
+#[test]
+fn valid_graphql() {
+    assert_eq!(
+        trim!(r#"
+            # Fetches the hero of an episode, and their friends
+            query Hero($episode: Episode = JEDI, $first: Int = 10) {
+                hero(episode: $episode) {
+                    name  # comments are removed
+                    ...Appearance
+                    friends(first: $first) {
+                        name,
+                        height(unit: METER)
+                    }
+                }
+            }
+
+            fragment Appearance on Character {
+                description(format: "markdown, # not a comment")
+                notes(text: """
+                    Block strings keep  their   content,
+                    even \""" escaped quotes
+                """)
+            }
+        "#, lang = graphql),
+        r#"query Hero($episode:Episode=JEDI$first:Int=10){hero(episode:$episode)"#
+        .to_owned()
+            + r#"{name ...Appearance friends(first:$first){name height(unit:METER)}}}"#
+            + r#"fragment Appearance on Character{description(format:"markdown, "#
+            + r#"# not a comment")notes(text:"""
+                    Block strings keep  their   content,
+                    even \""" escaped quotes
+                """)}"#,
+    );
+}
+
+#[test]
+fn valid_graphql_and_trailing_comma() {
+    assert_eq!(trim!("{ a, b }", lang = graphql,), "{a b}");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_lang() {
+    trim!("hello", lang = html);
+    trim!("hello", lang = "graphql");
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_lang_for_byte_string() {
+    trim!(b"{ a }", lang = graphql);
+    trim!(c"{ a }", lang = graphql);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_lang_with_join_with() {
+    trim!("{ a }", join_with = ' ', lang = graphql);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_unbalanced_graphql() {
+    trim!("
+        query {
+            user(id: 1 {
+                name
+            }
+        }
+    ", lang = graphql);
+    trim!("{ a } }", lang = graphql);
+    trim!("{ a(b: \"c) }", lang = graphql);
+}
+
+#[test]
+fn valid_regex() {
+    assert_eq!(
+        trim!(r"
+            ^
+            (?P<user> [\w.+-]+ )  # local part, e.g. `john.doe+tag`
+            @
+            (?P<host>
+                [a-z0-9-]+        # first label
+                (?: \. [a-z0-9-]+ )*
+            )
+            \ \#[ #]?             # an escaped space and hash, and a class
+            $
+        ", lang = regex),
+        r"^(?P<user>[\w.+-]+)@(?P<host>[a-z0-9-]+(?:\.[a-z0-9-]+)*)\ \#[ #]?$",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_regex() {
+    trim!(r"
+        ^ [a-z  # unclosed character class
+        $
+    ", lang = regex);
+    trim!(r"abc \", lang = regex);
+}
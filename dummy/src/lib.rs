@@ -9,3 +9,42 @@ mod c_string;
 
 #[cfg(test)]
 mod byte_string;
+
+#[cfg(test)]
+mod lang;
+
+#[cfg(test)]
+mod decode;
+
+#[cfg(test)]
+mod encode;
+
+#[cfg(test)]
+mod output;
+
+#[cfg(test)]
+mod lint;
+
+#[cfg(test)]
+mod invisible;
+
+#[cfg(test)]
+mod constraint;
+
+#[cfg(test)]
+mod trim_all;
+
+#[cfg(test)]
+mod file;
+
+#[cfg(test)]
+mod vars;
+
+#[cfg(test)]
+mod cfg;
+
+#[cfg(test)]
+mod define_trim;
+
+#[cfg(test)]
+mod assert;
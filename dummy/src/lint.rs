@@ -0,0 +1,148 @@
+use strim::trim;
+
+#[test]
+fn valid_allowed() {
+    assert_eq!(
+        trim!("hello", join_with = ' ', allow(needless_join_with, unchanged)),
+        "hello",
+    );
+    assert_eq!(trim!(b"hello", allow(unchanged,)), b"hello");
+    assert_eq!(
+        trim!("
+            \thello
+                world
+        ", join_with = ' ', allow(mixed_indentation)),
+        "hello world",
+    );
+    assert_eq!(
+        trim!(r"
+            hello \
+            world
+        ", join_with = ' ', allow(trailing_backslash)),
+        r"hello \ world",
+    );
+    assert_eq!(
+        trim!(c"hello\rworld", allow(lone_carriage_return, unchanged)),
+        c"hello\rworld",
+    );
+}
+
+#[test]
+fn valid_allowed_in_constant() {
+    const STRING: &str = trim!("hello", allow(unchanged));
+    assert_eq!(STRING, "hello");
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn needless_join_with() {
+    trim!("
+        hello
+    ", join_with = ' ');
+    trim!(b"hello", join_with = b' ');
+    trim!(c"", join_with = c", ");
+}
+
+#[test]
+fn valid_single_line() {
+    assert_eq!(concat!(trim!("hello"), ", world"), "hello, world");
+    assert!(matches!("hello", trim!("hello")));
+    assert_eq!(trim!(b"hello"), b"hello");
+    assert_eq!(trim!(c"hello"), c"hello");
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn unchanged() {
+    trim!("hello\nworld", join_with = '\n');
+    trim!(b"hello\nworld", join_with = b'\n');
+    trim!(c"hello\nworld", join_with = c"\n");
+
+    const STRING: &str = trim!("hello\nworld", join_with = "\n");
+    assert_eq!(STRING, "hello\nworld");
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn mixed_indentation() {
+    trim!("
+        hello
+	    world
+    ");
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn lone_carriage_return() {
+    trim!(b"
+        hello\r
+        wor\rld
+    ");
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn trailing_backslash() {
+    trim!(r"
+        hello \
+        world
+    ");
+}
+
+#[test]
+fn valid_debug_disabled() {
+    assert_eq!(
+        trim!("
+            Hello,
+            World!
+        ", join_with = ' ', debug = false),
+        "Hello, World!",
+    );
+}
+
+#[cfg(feature = "compile-warnings")]
+#[test]
+fn debug() {
+    assert_eq!(
+        trim!("
+
+            Hello,
+        \tW\u{f6}rld!
+        ", join_with = ' ', debug),
+        "Hello, W\u{f6}rld!",
+    );
+    assert_eq!(
+        trim!("
+            usr
+            local
+        ", join_with = MAIN_SEPARATOR, debug),
+        ["usr", "local"].join(std::path::MAIN_SEPARATOR_STR),
+    );
+    assert_eq!(trim!("{ a, b }", lang = graphql, debug = true), "{a b}");
+    assert_eq!(trim!(b"\r\n  48 69  \r\n", decode = hex, debug), b"Hi");
+    assert_eq!(trim!(c"a\nb", join_with = c"\r\n", debug), c"a\r\nb");
+    assert_eq!(
+        trim!("
+            Hello,
+            {name}!
+        ", join_with = ' ', vars = { name = "World" }, debug),
+        "Hello, World!",
+    );
+    assert_eq!(
+        trim!("
+            a
+            @cfg(unix) b
+            @cfg(windows) c
+        ", join_with = ' ', cfg_lines, debug),
+        if cfg!(unix) { "a b" } else if cfg!(windows) { "a c" } else { "a" },
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_allow() {
+    trim!("hello", allow(everything));
+    trim!("hello", allow = unchanged);
+    trim!("hello", allow(unchanged), allow(unchanged));
+    trim!("hello", debug = "true");
+}
@@ -0,0 +1,142 @@
+use strim::trim;
+
+#[test]
+fn valid_string_as_byte_string() {
+    assert_eq!(
+        trim!("
+            Hello,
+            W\u{f6}rld!
+        ", join_with = ' ', as = bytes),
+        b"Hello, W\xc3\xb6rld!",
+    );
+}
+
+#[test]
+fn valid_string_as_c_string() {
+    assert_eq!(
+        trim!("
+            Hello,
+            W\u{f6}rld!
+        ", join_with = "\r\n", as = c_str,),
+        c"Hello,\r\nW\xc3\xb6rld!",
+    );
+    assert_eq!(trim!("{ a }", lang = graphql, as = c_str), c"{a}");
+}
+
+#[test]
+fn valid_byte_string_as_string() {
+    assert_eq!(
+        trim!(b"
+            Hello,
+            W\xc3\xb6rld!
+        ", join_with = b' ', as = str),
+        "Hello, Wörld!",
+    );
+}
+
+#[test]
+fn valid_c_string_as_others() {
+    assert_eq!(
+        trim!(c"
+            Hello,
+            W\xc3\xb6rld!
+        ", join_with = b' ', as = str),
+        "Hello, Wörld!",
+    );
+    assert_eq!(
+        trim!(c"
+            Hello,
+            World!
+        ", as = bytes),
+        b"Hello,World!",
+    );
+}
+
+#[test]
+fn valid_decoded_as_others() {
+    assert_eq!(trim!("48 69", decode = hex, as = str), "Hi");
+    assert_eq!(trim!(b"SGk=", decode = base64, as = c_str), c"Hi");
+}
+
+#[test]
+fn valid_as_os_string_and_path() {
+    use std::{ffi::OsStr, path::{Path, MAIN_SEPARATOR_STR}};
+
+    let os_string: &OsStr = trim!("
+        Hello,
+        World!
+    ", join_with = ' ', as = os_str);
+    assert_eq!(os_string, "Hello, World!");
+
+    let path: &Path = trim!(b"
+        usr
+        local
+        bin
+    ", join_with = b'/', as = path);
+    assert_eq!(path, Path::new("usr/local/bin"));
+
+    let path: &Path = trim!("
+        usr
+        local
+        bin
+    ", join_with = MAIN_SEPARATOR, as = path);
+    let expected = ["usr", "local", "bin"].join(MAIN_SEPARATOR_STR);
+    assert_eq!(path, Path::new(&expected));
+    assert_eq!(path.components().count(), 3);
+}
+
+#[test]
+fn valid_const_path() {
+    use std::path::{Path, MAIN_SEPARATOR_STR};
+
+    const PATH: &str = trim!("
+        usr
+        local
+        bin
+    ", join_with = MAIN_SEPARATOR);
+    let expected = ["usr", "local", "bin"].join(MAIN_SEPARATOR_STR);
+    assert_eq!(Path::new(PATH), Path::new(&expected));
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_as() {
+    trim!("hello", as = string);
+    trim!("hello", as = "str");
+    trim!("hello", encode = utf16, as = str);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_nul_in_c_string() {
+    trim!("
+        hello
+        wor\0ld
+    ", as = c_str);
+    trim!(b"hello", join_with = b'\0', as = c_str);
+    trim!("00 01", decode = hex, as = c_str);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_utf8_in_string() {
+    trim!(b"
+        hello
+        wor\xffld
+    ", as = str);
+    trim!(c"hello", join_with = c"\xff", as = str);
+    trim!("ff", decode = hex, as = str);
+    trim!(b"wor\xffld", as = path);
+    trim!(c"wor\xffld", as = os_str);
+}
+
+#[test]
+fn valid_c_string_in_constant() {
+    use std::ffi::CStr;
+
+    const C_STRING: &CStr = trim!(b"
+        Hello,
+        World!
+    ", join_with = b' ', as = c_str);
+    assert_eq!(C_STRING, c"Hello, World!");
+}
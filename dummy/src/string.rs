@@ -176,3 +176,30 @@ fn invalid_trailing_commas_after_join_with() {
     trim!("hello", join_with = '\n',,);
     trim!("hello", join_with = "\r\n",,);
 }
+
+#[test]
+fn valid_joined_with_main_separator() {
+    use std::path::MAIN_SEPARATOR_STR;
+
+    assert_eq!(
+        trim!("
+            usr
+            local
+
+            bin
+        ", join_with = MAIN_SEPARATOR),
+        ["usr", "local", "bin"].join(MAIN_SEPARATOR_STR),
+    );
+    assert_eq!(
+        trim!("  usr  ", join_with = MAIN_SEPARATOR, allow(needless_join_with)),
+        "usr",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_main_separator() {
+    trim!("hello", join_with = MAIN_SEPARATORS);
+    trim!(b"hello", join_with = MAIN_SEPARATOR);
+    trim!("hello", join_with = MAIN_SEPARATOR, lang = regex);
+}
@@ -0,0 +1,119 @@
+use std::ffi::CStr;
+use strim::trim_all;
+
+#[trim_all]
+mod everything {
+    use std::ffi::CStr;
+
+    /// Doc comments are
+    ///     left alone
+    #[doc = "
+        and so are attributes
+    "]
+    pub const STRING: &str = "
+        Hello,
+        World!
+    ";
+    pub static BYTE_STRING: &[u8] = b"
+        Hello,\r
+        World!\r
+    ";
+    pub const C_STRING: &CStr = c"
+        Hello,
+        World!
+    ";
+
+    pub fn greeting(name: &str) -> &'static str {
+        match name {
+            "  World  " => "  Hello  ",
+            _ => "  Bye  ",
+        }
+    }
+}
+
+#[trim_all(join_with = ' ')]
+const JOINED: [&str; 2] = [
+    "
+        SELECT name
+        FROM users
+    ",
+    r#"
+        {"name":
+         "strim"}
+    "#,
+];
+
+#[trim_all(join_with = ", ")]
+const JOINED_BYTES: (&[u8], &CStr) = (b"a\n  b\n  c", c"a\n  b\n  c");
+
+#[trim_all(only = hashed)]
+const HASHED: [&str; 2] = [r#" a "#, " a "];
+
+#[trim_all(only = multiline, join_with = ' ')]
+const MULTILINE: [&str; 2] = [" a \n b ", " a "];
+
+struct Queries;
+
+#[trim_all(join_with = ' ')]
+impl Queries {
+    const ALL: &'static str = "
+        SELECT *
+        FROM users
+    ";
+
+    fn by_id(&self) -> String {
+        let condition = "
+            WHERE id = ?
+        ";
+        // NOTE: The arguments of macros are left alone
+        format!("{}\n{}", Self::ALL, condition)
+    }
+}
+
+#[test]
+fn valid() {
+    assert_eq!(everything::STRING, "Hello,World!");
+    assert_eq!(everything::BYTE_STRING, b"Hello,World!");
+    assert_eq!(everything::C_STRING, c"Hello,World!");
+    assert_eq!(everything::greeting("  World  "), "Hello");
+    assert_eq!(everything::greeting("World"), "Bye");
+}
+
+#[test]
+fn valid_joined() {
+    assert_eq!(JOINED, ["SELECT name FROM users", r#"{"name": "strim"}"#]);
+    assert_eq!(JOINED_BYTES, (b"a, b, c".as_slice(), c"a, b, c"));
+    assert_eq!(
+        Queries.by_id(),
+        "SELECT * FROM users\nWHERE id = ?",
+    );
+}
+
+#[test]
+fn valid_only() {
+    assert_eq!(HASHED, ["a", " a "]);
+    assert_eq!(MULTILINE, ["a b", " a "]);
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_parameters() {
+    #[trim_all(join_with = MAIN_SEPARATOR, only = all, lang = graphql)]
+    const STRING: &str = "a";
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_delimiter() {
+    #[trim_all(join_with = '\0')]
+    const C_STRING: &CStr = c"a\nb";
+    #[trim_all(join_with = '\u{200b}')]
+    const STRING: &str = "a\nb";
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_invisible() {
+    #[trim_all]
+    const STRINGS: [&str; 2] = ["a\u{200b}", "b\u{202e}"];
+}
@@ -0,0 +1,66 @@
+use strim::trim;
+
+const HELP: &str = trim!("
+    {name} {version}
+    Usage: {name} {{FILE}}
+", join_with = ' ', vars = {
+    name = env("CARGO_PKG_NAME"),
+    version = env("CARGO_PKG_VERSION"),
+});
+
+#[test]
+fn valid() {
+    assert_eq!(HELP, "tests 0.0.0 Usage: tests {FILE}");
+    assert_eq!(
+        trim!("
+            query {{ user(name: \"{name}\") {{ id }} }}
+        ", lang = graphql, vars = { name = "strim" }),
+        "query{user(name:\"strim\"){id}}",
+    );
+    assert_eq!(trim!("{{a}}", vars = {}), "{a}");
+    assert_eq!(trim!("  {a}  ", vars = { a = "  b  " }), "  b  ");
+}
+
+#[test]
+fn valid_constraints() {
+    assert_eq!(
+        trim!("{a}{a}", vars = { a = "abc" }, max_len = 6),
+        "abcabc",
+    );
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_placeholders() {
+    trim!("{b}", vars = { a = "a" });
+    trim!("{a", vars = { a = "a" });
+    trim!("a}", vars = { a = "a" });
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_vars() {
+    trim!("{a}", vars = { a = env("STRIM_SURELY_NOT_SET") });
+    trim!("{a}", vars = { a = "a", a = "b" });
+    trim!("{a}", vars = { a = 1 });
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_values() {
+    trim!("a\n{a}", vars = { a = "\0" }, as = c_str);
+    trim!("{a}", vars = { a = "b\u{200b}" });
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_parameters() {
+    trim!(b"{a}", vars = { a = "a" });
+    trim!("{a}", decode = hex, vars = { a = "a" });
+}
+
+#[cfg(feature = "compile-errors")]
+#[test]
+fn invalid_max_len() {
+    trim!("{a}", vars = { a = "abc" }, max_len = 2);
+}
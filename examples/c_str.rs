@@ -0,0 +1,15 @@
+//! Produces a constant C-string, which relies on the fallback emission on
+//! toolchains before 1.79, and is built by the CI with the minimum supported
+//! version of Rust to make sure that the fallback can be used in const
+//! contexts.
+use std::ffi::CStr;
+use strim::trim;
+
+const GREETING: &CStr = trim!("
+    Hello,
+    World!
+", join_with = ' ', as = c_str);
+
+fn main() {
+    println!("{GREETING:?}");
+}
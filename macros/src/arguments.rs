@@ -0,0 +1,307 @@
+use std::ffi::CString;
+use syn::{
+    Error,
+    LitStr,
+    LitCStr,
+    LitByteStr,
+    Lit::{
+        self,
+        Str,
+        CStr,
+        ByteStr,
+    },
+    braced,
+    parse::{
+        Parse,
+        Parser,
+        ParseStream,
+    },
+};
+use proc_macro2::{Span, Punct, Spacing, TokenTree, TokenStream};
+use strim_core::transform;
+use super::{
+    parameters::{Parameters, enabled},
+    string,
+    c_string,
+    byte_string,
+    lint::Lint,
+    spans::Spans,
+    metadata::{Kind, Metadata},
+};
+
+#[derive(Clone)]
+pub enum Arguments {
+    String {
+        literal: LitStr,
+        parameters: Parameters<string::Delimiter>,
+    },
+    CString {
+        literal: LitCStr,
+        parameters: Parameters<c_string::Delimiter>,
+    },
+    ByteString {
+        literal: LitByteStr,
+        parameters: Parameters<byte_string::Delimiter>,
+    },
+}
+
+impl Arguments {
+    pub fn allowed(&self) -> Vec<Lint> {
+        let allow = match self {
+            Self::String { parameters, .. } => &parameters.allow,
+            Self::CString { parameters, .. } => &parameters.allow,
+            Self::ByteString { parameters, .. } => &parameters.allow,
+        };
+
+        allow
+            .as_ref()
+            .map(|allow| allow.value.lints.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn allows_invisible(&self) -> bool {
+        let allow_invisible = match self {
+            Self::String { parameters, .. } => &parameters.allow_invisible,
+            Self::CString { parameters, .. } => &parameters.allow_invisible,
+            Self::ByteString { parameters, .. } => &parameters.allow_invisible,
+        };
+
+        enabled(allow_invisible).is_some()
+    }
+
+    pub fn has_cfg_lines(&self) -> bool {
+        let cfg_lines = match self {
+            Self::String { parameters, .. } => &parameters.cfg_lines,
+            Self::CString { parameters, .. } => &parameters.cfg_lines,
+            Self::ByteString { parameters, .. } => &parameters.cfg_lines,
+        };
+
+        enabled(cfg_lines).is_some()
+    }
+
+    /// Returns the value of the literal.
+    pub fn value(&self) -> Vec<u8> {
+        match self {
+            Self::String { literal, .. } => literal.value().into_bytes(),
+            Self::CString { literal, .. } => literal.value().into_bytes(),
+            Self::ByteString { literal, .. } => literal.value(),
+        }
+    }
+
+    /// Returns the spans of the parts of the literal.
+    pub fn spans(&self) -> Spans {
+        match self {
+            Self::String { literal, .. } => Spans::new(literal.token()),
+            Self::CString { literal, .. } => Spans::new(literal.token()),
+            Self::ByteString { literal, .. } => Spans::new(literal.token()),
+        }
+    }
+
+    /// Returns the same arguments, but with a literal of the same kind, whose
+    /// value is `value`, which has to be a subset of the lines of the value of
+    /// the original literal.
+    pub fn with_value(&self, value: Vec<u8>) -> Self {
+        let span = self.span();
+        let mut arguments = self.clone();
+
+        match &mut arguments {
+            Self::String { literal, .. } => {
+                // NOTE: Whole lines of a string are valid UTF-8 as well
+                let value = String::from_utf8(value)
+                    .expect("Invalid UTF-8 in the lines of a string");
+                *literal = LitStr::new(&value, span);
+            },
+            Self::CString { literal, .. } => {
+                let value = CString::new(value)
+                    .expect("Nul character in the lines of a C-string");
+                *literal = LitCStr::new(&value, span);
+            },
+            Self::ByteString { literal, .. } => {
+                *literal = LitByteStr::new(&value, span);
+            },
+        }
+
+        arguments
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Self::String { literal, .. } => literal.span(),
+            Self::CString { literal, .. } => literal.span(),
+            Self::ByteString { literal, .. } => literal.span(),
+        }
+    }
+}
+
+/// Returns the name (if any) and the tokens of each named parameter among
+/// `tokens`, which are separated by commas.
+pub fn named_parameters_of(tokens: TokenStream) -> Vec<(String, TokenStream)> {
+    let mut parameters = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    let is_comma = |token: &TokenTree| {
+        matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')
+    };
+
+    while tokens.peek().is_some() {
+        let parameter: TokenStream =
+            tokens.by_ref().take_while(|token| !is_comma(token)).collect();
+        let name = match parameter.clone().into_iter().next() {
+            Some(TokenTree::Ident(name)) => name.to_string(),
+            Some(_) => String::new(),
+            None => continue,
+        };
+        parameters.push((name, parameter));
+    }
+
+    parameters
+}
+
+/// Returns the tokens of the named parameters of `defaults` which are not
+/// named in `overridden`, each of which is preceded by a comma.
+fn remaining(defaults: TokenStream, overridden: &[String]) -> TokenStream {
+    let mut remaining = TokenStream::new();
+    for (name, tokens) in named_parameters_of(defaults) {
+        if !overridden.contains(&name) {
+            let comma = Punct::new(',', Spacing::Alone);
+            remaining.extend([TokenTree::from(comma)]);
+            remaining.extend(tokens);
+        }
+    }
+
+    remaining
+}
+
+/// Parses the named parameters of `stream`, and takes the ones which are not
+/// set from `defaults`, i.e. the named parameters of a preset (defined by
+/// `define_trim!`), then from `metadata`, i.e. the ones of the manifest.  The
+/// overridden defaults are not even parsed, as they may not be valid for the
+/// literal.  The errors of `check` (which depend on the kind of the literal)
+/// are reported together with all the other errors of the parameters.
+fn with_defaults<D>(
+    stream: ParseStream,
+    defaults: TokenStream,
+    metadata: TokenStream,
+    check: impl Fn(&Parameters<D>) -> syn::Result<()>,
+) -> syn::Result<Parameters<D>>
+where
+    D: Parse,
+{
+    if defaults.is_empty() && metadata.is_empty() {
+        return Parameters::parse_checked(stream, check);
+    }
+
+    let mut overridden: Vec<_> =
+        named_parameters_of(stream.fork().parse::<TokenStream>()?)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+    let parameters = Parameters::parse_checked(stream, &check)?;
+    let defaults = remaining(defaults, &overridden);
+    let parameters = parameters.or(Parameters::parse.parse2(defaults.clone())?);
+
+    overridden.extend(
+        named_parameters_of(defaults).into_iter().map(|(name, _)| name),
+    );
+    // NOTE: The delimiter of the manifest is not meant to be a conflict when
+    //       an invocation uses a parameter which cannot be combined with it
+    if parameters.lang.is_some() || parameters.decode.is_some() {
+        overridden.push(String::from("join_with"));
+    }
+    let metadata = remaining(metadata, &overridden);
+    let parameters = parameters.or(Parameters::parse.parse2(metadata)?);
+    check(&parameters)?;
+
+    Ok(parameters)
+}
+
+impl Arguments {
+    /// Parses the literal and its named parameters, where the ones which are
+    /// not set are taken from `defaults`, then from the metadata of the
+    /// manifest.
+    fn parse_with_defaults(
+        stream: ParseStream,
+        defaults: TokenStream,
+    ) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either a string, a byte-string, or a C-string literal";
+
+        let metadata = Metadata::load()?;
+
+        match Lit::parse(stream) {
+            Ok(Str(literal)) => {
+                let metadata = metadata.to_parameters(Kind::String)?;
+                let value = literal.value().into_bytes();
+                let spans = Spans::new(literal.token());
+                let check = |parameters: &Parameters<_>| {
+                    parameters.check(
+                        transform::Literal::String,
+                        string::Delimiter::to_delimiter,
+                        &value,
+                        &spans,
+                    )
+                };
+                let parameters =
+                    with_defaults(stream, defaults, metadata, check)?;
+
+                Ok(Self::String { literal, parameters })
+            },
+            Ok(CStr(literal)) => {
+                let metadata = metadata.to_parameters(Kind::CString)?;
+                let value = literal.value().into_bytes();
+                let spans = Spans::new(literal.token());
+                let check = |parameters: &Parameters<_>| {
+                    parameters.check(
+                        transform::Literal::CString,
+                        c_string::Delimiter::to_delimiter,
+                        &value,
+                        &spans,
+                    )
+                };
+                let parameters =
+                    with_defaults(stream, defaults, metadata, check)?;
+
+                Ok(Self::CString { literal, parameters })
+            },
+            Ok(ByteStr(literal)) => {
+                let metadata = metadata.to_parameters(Kind::ByteString)?;
+                let value = literal.value();
+                let spans = Spans::new(literal.token());
+                let check = |parameters: &Parameters<_>| {
+                    parameters.check(
+                        transform::Literal::ByteString,
+                        byte_string::Delimiter::to_delimiter,
+                        &value,
+                        &spans,
+                    )
+                };
+                let parameters =
+                    with_defaults(stream, defaults, metadata, check)?;
+
+                Ok(Self::ByteString { literal, parameters })
+            },
+            Ok(any) => Err(Error::new_spanned(any, MESSAGE)),
+            Err(error) => Err(Error::new(error.span(), MESSAGE)),
+        }
+    }
+}
+
+impl Parse for Arguments {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        Self::parse_with_defaults(stream, TokenStream::new())
+    }
+}
+
+/// The arguments of a macro defined by `define_trim!`, i.e. the named
+/// parameters of the preset in braces, followed by the arguments of `trim!`,
+/// whose named parameters override the ones of the preset.
+pub struct Preset(pub Arguments);
+
+impl Parse for Preset {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in stream);
+        let defaults = content.parse::<TokenStream>()?;
+
+        Arguments::parse_with_defaults(stream, defaults).map(Self)
+    }
+}
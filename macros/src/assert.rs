@@ -0,0 +1,154 @@
+use syn::{
+    Error,
+    Expr,
+    Ident,
+    parenthesized,
+    token::Comma,
+    parse::{
+        Parse,
+        Parser,
+        ParseStream,
+    },
+};
+use quote::quote;
+use proc_macro2::{Punct, Spacing, TokenTree, TokenStream};
+use strim_core::transform;
+use super::{
+    string::Delimiter,
+    arguments::{Arguments, named_parameters_of},
+    parameters::{Flag, Parameter, enabled, parse_flag, set_once},
+};
+
+/// The arguments of `assert_trimmed_eq!`, i.e. the path of the `strim` crate
+/// in parentheses (as it is passed by the exported macro), the actual value,
+/// and the arguments of `trim!` for the expected value, whose named parameters
+/// can include `trim_actual` as well.
+pub struct Assertion {
+    krate: TokenStream,
+    actual: Expr,
+    trim_actual: Option<Parameter<Flag>>,
+    pub arguments: Arguments,
+}
+
+impl Parse for Assertion {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in stream);
+        let krate = content.parse::<TokenStream>()?;
+
+        let actual = Expr::parse(stream)?;
+        Comma::parse(stream)?;
+
+        let mut trim_actual = None;
+        let mut rest = TokenStream::new();
+        for (name, tokens) in named_parameters_of(stream.parse()?) {
+            if name == "trim_actual" {
+                let flag = (|stream: ParseStream| {
+                    let identifier = Ident::parse(stream)?;
+                    parse_flag(stream, identifier)
+                })
+                .parse2(tokens)?;
+                set_once(&mut trim_actual, flag)?;
+                continue;
+            }
+
+            if !rest.is_empty() {
+                let comma = Punct::new(',', Spacing::Alone);
+                rest.extend([TokenTree::from(comma)]);
+            }
+            rest.extend(tokens);
+        }
+        let arguments = Arguments::parse.parse2(rest)?;
+
+        let Arguments::String { parameters, .. } = &arguments else {
+            let message = "Expected a string literal";
+            return Err(Error::new(arguments.span(), message));
+        };
+        let names = [
+            parameters.decode.as_ref().map(|decode| &decode.name),
+            parameters.encode.as_ref().map(|encode| &encode.name),
+            parameters.output.as_ref().map(|output| &output.name),
+        ];
+        if let Some(name) = names.into_iter().flatten().next() {
+            let message = format!(
+                "Named parameter `{name}` cannot be used with \
+                 `assert_trimmed_eq`, as the expected value has to be a string",
+            );
+            return Err(Error::new_spanned(name, message));
+        }
+
+        Ok(Self { krate, actual, trim_actual, arguments })
+    }
+}
+
+impl Assertion {
+    /// Produces the options with which the actual value is trimmed at run-time
+    /// (if it is trimmed at all), i.e. the same `join_with`, `lang`, and
+    /// `allow_invisible` as the ones of the expected value.
+    fn options(&self) -> TokenStream {
+        let krate = &self.krate;
+        let Arguments::String { parameters, .. } = &self.arguments else {
+            unreachable!("The expected value is not a string");
+        };
+        if enabled(&self.trim_actual).is_none() {
+            return quote!(::core::option::Option::None);
+        }
+
+        let join_with = match parameters.join_with.as_ref().map(|join_with| {
+            &join_with.value
+        }) {
+            Some(Delimiter::Character(character)) => quote! {
+                ::core::option::Option::Some(
+                    #krate::build::Delimiter::from(#character),
+                )
+            },
+            Some(Delimiter::String(string)) => quote! {
+                ::core::option::Option::Some(
+                    #krate::build::Delimiter::from(#string),
+                )
+            },
+            Some(Delimiter::MainSeparator) => quote! {
+                ::core::option::Option::Some(
+                    #krate::build::Delimiter::MainSeparator,
+                )
+            },
+            None => quote!(::core::option::Option::None),
+        };
+        let lang = match parameters.lang.as_ref().map(|lang| lang.value.0) {
+            Some(transform::Lang::GraphQl) => quote! {
+                ::core::option::Option::Some(#krate::build::Lang::GraphQl)
+            },
+            Some(transform::Lang::Regex) => quote! {
+                ::core::option::Option::Some(#krate::build::Lang::Regex)
+            },
+            None => quote!(::core::option::Option::None),
+        };
+        let allow_invisible = self.arguments.allows_invisible();
+
+        quote! {
+            ::core::option::Option::Some(&#krate::build::Options {
+                join_with: #join_with,
+                lang: #lang,
+                allow_invisible: #allow_invisible,
+                ..::core::default::Default::default()
+            })
+        }
+    }
+
+    /// Produces the comparison of the actual value to `expected`, i.e. to the
+    /// expansion of `trim!` for the arguments.
+    pub fn expand(self, expected: TokenStream) -> TokenStream {
+        let options = self.options();
+        let Self { krate, actual, .. } = self;
+
+        quote! {
+            match (&(#actual), #expected) {
+                (actual, expected) => #krate::__compare_trimmed(
+                    ::core::convert::AsRef::<str>::as_ref(actual),
+                    expected,
+                    #options,
+                ),
+            }
+        }
+    }
+}
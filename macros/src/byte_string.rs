@@ -10,13 +10,27 @@ use syn::{
         ParseStream,
     },
 };
-use super::bytes::{self, Bytes as _};
+use strim_core::transform;
 
+#[derive(Clone)]
 pub enum Delimiter {
     Byte(u8),
     ByteString(Vec<u8>),
 }
 
+impl Delimiter {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Byte(byte) => vec![*byte],
+            Self::ByteString(byte_string) => byte_string.clone(),
+        }
+    }
+
+    pub fn to_delimiter(&self) -> transform::Delimiter {
+        transform::Delimiter::Bytes(self.to_bytes())
+    }
+}
+
 impl Default for Delimiter {
     fn default() -> Self {
         Self::ByteString(Vec::new())
@@ -36,27 +50,3 @@ impl Parse for Delimiter {
         }
     }
 }
-
-pub struct Lines<'a> {
-    lines: bytes::Lines<'a>,
-}
-
-impl<'a> From<&'a [u8]> for Lines<'a> {
-    fn from(bytes: &'a [u8]) -> Self {
-        Self { lines: bytes.lines() }
-    }
-}
-
-impl<'a> Iterator for Lines<'a> {
-    type Item = &'a [u8];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let line = self.lines.next()?;
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed)
-            }
-        }
-    }
-}
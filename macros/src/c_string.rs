@@ -11,8 +11,9 @@ use syn::{
         ParseStream,
     },
 };
-use super::bytes::{self, Bytes as _};
+use strim_core::transform;
 
+#[derive(Clone)]
 pub enum Delimiter {
     // NOTE: It feels really rather wrong to look for a byte-literal, instead of
     //       whatever `c_char` would resolve to on the given platform (i.e
@@ -26,9 +27,22 @@ pub enum Delimiter {
     CString(CString),
 }
 
+impl Delimiter {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Byte(byte) => vec![*byte],
+            Self::CString(c_string) => c_string.to_bytes().to_vec(),
+        }
+    }
+
+    pub fn to_delimiter(&self) -> transform::Delimiter {
+        transform::Delimiter::Bytes(self.to_bytes())
+    }
+}
+
 impl Default for Delimiter {
     fn default() -> Self {
-        Self::CString(c"".to_owned())
+        Self::CString(CString::default())
     }
 }
 
@@ -50,27 +64,3 @@ impl Parse for Delimiter {
         }
     }
 }
-
-pub struct Lines<'a> {
-    lines: bytes::Lines<'a>,
-}
-
-impl<'a> From<&'a [u8]> for Lines<'a> {
-    fn from(bytes: &'a [u8]) -> Self {
-        Self { lines: bytes.lines() }
-    }
-}
-
-impl<'a> Iterator for Lines<'a> {
-    type Item = &'a [u8];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let line = self.lines.next()?;
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed)
-            }
-        }
-    }
-}
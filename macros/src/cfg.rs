@@ -0,0 +1,157 @@
+use std::mem;
+use syn::{Error, Meta};
+use quote::ToTokens;
+use proc_macro::{
+    Group,
+    Delimiter,
+    TokenTree,
+    TokenStream,
+};
+use strim_core::cfg;
+use super::{
+    arguments::Arguments,
+    invalid::IntoError,
+    lint::Lint,
+    spans::Spans,
+};
+
+/// The lines of a literal, some of which are only kept if their `cfg`
+/// predicate holds.
+struct CfgLines<'a> {
+    lines: cfg::CfgLines<'a>,
+    predicates: Vec<Meta>,
+}
+
+impl<'a> CfgLines<'a> {
+    /// Collects the lines of `value`, i.e. of the (unescaped) value of the
+    /// literal `spans` belong to.
+    fn new(value: &'a [u8], spans: &Spans) -> syn::Result<Self> {
+        let lines = cfg::CfgLines::new(value)
+            .map_err(|invalid| invalid.into_error(value, spans))?;
+        let predicates = lines
+            .predicates()
+            .iter()
+            .map(|predicate| {
+                let range = predicate.range.clone();
+                syn::parse_str::<Meta>(&predicate.text).map_err(|_| {
+                    let text = String::from_utf8_lossy(&value[range.clone()]);
+                    let message = format!("Invalid `cfg` predicate `{text}`");
+                    Error::new(spans.subspan(range), message)
+                })
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self { lines, predicates })
+    }
+
+/// Returns the `cfg` predicate which only holds for the variant `holds`,
+    /// i.e. the one whose bits are whether the corresponding predicates hold.
+    fn predicate_of(&self, holds: usize) -> String {
+        let predicates: Vec<_> = self
+            .predicates
+            .iter()
+            .enumerate()
+            .map(|(index, predicate)| {
+                let predicate = predicate.to_token_stream();
+                match holds & (1 << index) {
+                    0 => format!("not({predicate})"),
+                    _ => predicate.to_string(),
+                }
+            })
+            .collect();
+
+        format!("all({})", predicates.join(", "))
+    }
+
+    /// Produces nested `if cfg!(...)` expressions which select the expression
+    /// of the variant whose predicates hold, where `expressions` are in the
+    /// same order as the variants.
+    fn selected(&self, mut expressions: Vec<TokenStream>) -> TokenStream {
+        self.selected_from(0, 0, &mut expressions)
+    }
+
+    fn selected_from(
+        &self,
+        index: usize,
+        holds: usize,
+        expressions: &mut Vec<TokenStream>,
+    ) -> TokenStream {
+        let Some(predicate) = self.predicates.get(index) else {
+            return mem::take(&mut expressions[holds]);
+        };
+
+        let condition = format!(
+            "if ::core::cfg!({})",
+            predicate.to_token_stream(),
+        );
+        let [condition, otherwise] = [condition.as_str(), "else"]
+            .map(|code| code.parse().expect("Invalid code"));
+        let [holding, others] = [holds | (1 << index), holds].map(|holds| {
+            let branch = self.selected_from(index + 1, holds, expressions);
+            TokenStream::from(TokenTree::from(Group::new(
+                Delimiter::Brace,
+                branch,
+            )))
+        });
+
+        TokenStream::from_iter([condition, holding, otherwise, others])
+    }
+}
+
+/// Expands every variant of the literal of `arguments` with `expand` (without
+/// the warnings of the `allowed` lints, and with the preview only reported if
+/// the given `cfg` predicate holds), and produces the selection of the one
+/// whose predicates hold.
+pub fn expanded(
+    arguments: Arguments,
+    allowed: &[Lint],
+    expand: impl Fn(Arguments, &[Lint], Option<String>)
+        -> syn::Result<TokenStream>,
+) -> syn::Result<TokenStream> {
+    let value = arguments.value();
+    let lines = CfgLines::new(&value, &arguments.spans())?;
+    let mut variants = lines.lines.variants().enumerate().peekable();
+    let mut expressions = Vec::new();
+    while let Some((holds, variant)) = variants.next() {
+        // NOTE: Only the last variant, in which all the predicates hold, has
+        //       all the lines, so the others would only repeat its warnings,
+        //       while none of them is the same as the literal with the markers
+        let allowed = match variants.peek() {
+            Some(_) => Lint::ALL.to_vec(),
+            None => [allowed, &[Lint::Unchanged]].concat(),
+        };
+        // NOTE: Every variant is compiled, so only the preview of the selected
+        //       one is kept by `#[cfg(...)]`
+        let predicate = lines.predicate_of(holds);
+        let arguments = arguments.with_value(variant);
+        expressions.push(expand(arguments, &allowed, Some(predicate))?);
+    }
+
+    Ok(lines.selected(expressions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicates(value: &str) -> syn::Result<Vec<String>> {
+        let spans = Spans::new(proc_macro2::Literal::string(value));
+        let lines = CfgLines::new(value.as_bytes(), &spans)?;
+
+        Ok(lines
+            .predicates
+            .iter()
+            .map(|predicate| predicate.to_token_stream().to_string())
+            .collect())
+    }
+
+    #[test]
+    fn parsed_predicates() {
+        assert_eq!(
+            predicates("@cfg(unix) a\n@cfg( unix )b\n@cfg(feature = \"x\")c")
+                .unwrap(),
+            ["unix", "feature = \"x\""],
+        );
+        assert!(predicates("a\n@cfg(1 +) b").is_err());
+    }
+}
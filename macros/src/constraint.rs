@@ -0,0 +1,89 @@
+use syn::{
+    Error,
+    LitInt,
+    LitStr,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use strim_core::constraint;
+use super::parameters::{Parameter, Flag, enabled};
+
+/// A non-negative integer value of a named parameter.
+#[derive(Clone)]
+pub struct Limit(pub usize);
+
+impl Parse for Limit {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str = "Expected a non-negative integer literal";
+
+        LitInt::parse(stream)
+            .and_then(|literal| literal.base10_parse())
+            .map(Self)
+            .map_err(|error| Error::new(error.span(), MESSAGE))
+    }
+}
+
+/// The characters listed by `forbid = "..."`.
+#[derive(Clone)]
+pub struct Forbidden(pub Vec<char>);
+
+impl Parse for Forbidden {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected a non-empty string literal of the forbidden characters";
+
+        let literal = stream
+            .parse::<LitStr>()
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+        let characters: Vec<_> = literal.value().chars().collect();
+        if characters.is_empty() {
+            return Err(Error::new_spanned(literal, MESSAGE));
+        }
+
+        Ok(Self(characters))
+    }
+}
+
+/// The requirements the trimmed output has to meet.
+#[derive(Clone, Default)]
+pub struct Constraints {
+    pub max_len: Option<Parameter<Limit>>,
+    pub min_lines: Option<Parameter<Limit>>,
+    pub ascii_only: Option<Parameter<Flag>>,
+    pub forbid: Option<Parameter<Forbidden>>,
+    pub require_utf8: Option<Parameter<Flag>>,
+}
+
+impl Constraints {
+    /// Returns the constraints with the ones not set taken from `defaults`.
+    pub fn or(self, defaults: Self) -> Self {
+        Self {
+            max_len: self.max_len.or(defaults.max_len),
+            min_lines: self.min_lines.or(defaults.min_lines),
+            ascii_only: self.ascii_only.or(defaults.ascii_only),
+            forbid: self.forbid.or(defaults.forbid),
+            require_utf8: self.require_utf8.or(defaults.require_utf8),
+        }
+    }
+
+    /// Returns the requirements as the ones of the transformations.
+    pub fn to_constraints(&self) -> constraint::Constraints {
+        let limit = |limit: &Option<Parameter<Limit>>| {
+            limit.as_ref().map(|limit| limit.value.0)
+        };
+
+        constraint::Constraints {
+            max_len: limit(&self.max_len),
+            min_lines: limit(&self.min_lines),
+            ascii_only: enabled(&self.ascii_only).is_some(),
+            forbid: self
+                .forbid
+                .as_ref()
+                .map(|forbid| forbid.value.0.clone())
+                .unwrap_or_default(),
+            require_utf8: enabled(&self.require_utf8).is_some(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use syn::{
+    Error,
+    Ident,
+    LitStr,
+    parenthesized,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use strim_core::{base64::Alphabet, transform};
+
+/// The value of `decode = ...`.
+#[derive(Clone)]
+pub struct Decode(pub transform::Decode);
+
+impl Parse for Decode {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either `hex`, `base64`, `base64_url`, or `pem(\"...\")` \
+             as a decoding";
+
+        let identifier = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+
+        match identifier.to_string().as_str() {
+            "hex" => Ok(Self(transform::Decode::Hex)),
+            "base64" => {
+                Ok(Self(transform::Decode::Base64(Alphabet::Standard)))
+            },
+            "base64_url" => {
+                Ok(Self(transform::Decode::Base64(Alphabet::UrlSafe)))
+            },
+            "pem" => {
+                let content;
+                parenthesized!(content in stream);
+
+                let label = content.parse::<LitStr>().map_err(|error| {
+                    let message =
+                        "Expected the label of the PEM block as a string \
+                         literal, e.g. `pem(\"CERTIFICATE\")`";
+                    Error::new(error.span(), message)
+                })?;
+
+                if !content.is_empty() {
+                    let message = "Expected `)` to follow";
+                    return Err(Error::new(content.span(), message));
+                }
+
+                Ok(Self(transform::Decode::Pem(label.value())))
+            },
+            _ => Err(Error::new_spanned(identifier, MESSAGE)),
+        }
+    }
+}
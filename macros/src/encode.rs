@@ -0,0 +1,69 @@
+use syn::{
+    Error,
+    Ident,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro::{
+    Group,
+    Punct,
+    Literal,
+    Spacing,
+    Delimiter,
+    TokenTree,
+    TokenStream,
+};
+use strim_core::transform;
+
+/// The value of `encode = ...`.
+#[derive(Clone)]
+pub struct Encode(pub transform::Encode);
+
+fn array_reference_of(elements: impl Iterator<Item = Literal>) -> TokenStream {
+    let elements = elements
+        .flat_map(|element| [
+            TokenTree::from(element),
+            TokenTree::from(Punct::new(',', Spacing::Alone)),
+        ])
+        .collect();
+
+    TokenStream::from_iter([
+        TokenTree::from(Punct::new('&', Spacing::Alone)),
+        TokenTree::from(Group::new(Delimiter::Bracket, elements)),
+    ])
+}
+
+/// Produces a reference to an array of the code units of `string` encoded as
+/// `encode`, i.e. either `&[u16; N]` or `&[u32; N]`.
+pub fn encoded(encode: transform::Encode, string: &str) -> TokenStream {
+    let units = encode.units(string).into_iter();
+    match encode {
+        transform::Encode::Utf32 => {
+            array_reference_of(units.map(Literal::u32_suffixed))
+        },
+        transform::Encode::Utf16 | transform::Encode::Utf16Nul => {
+            // NOTE: The code units of UTF-16 fit in a `u16`
+            let units = units.map(|unit| unit as u16);
+            array_reference_of(units.map(Literal::u16_suffixed))
+        },
+    }
+}
+
+impl Parse for Encode {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either `utf16`, `utf16_nul`, or `utf32` as an encoding";
+
+        let identifier = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+
+        match identifier.to_string().as_str() {
+            "utf16" => Ok(Self(transform::Encode::Utf16)),
+            "utf16_nul" => Ok(Self(transform::Encode::Utf16Nul)),
+            "utf32" => Ok(Self(transform::Encode::Utf32)),
+            _ => Err(Error::new_spanned(identifier, MESSAGE)),
+        }
+    }
+}
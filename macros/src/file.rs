@@ -0,0 +1,383 @@
+use std::{
+    env,
+    fs,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+use syn::{
+    Error,
+    Ident,
+    LitInt,
+    LitStr,
+    Token,
+    token::Comma,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use strim_core::{
+    files::files_in,
+    select,
+    transform::{self, Options},
+};
+use super::{
+    string::Delimiter,
+    lang::Lang,
+    parameters::{
+        Flag,
+        Parameter,
+        combined,
+        enabled,
+        exclusive,
+        listed,
+        parse_flag,
+        parse_value,
+        set_once,
+        skip_to_comma,
+    },
+};
+
+const NAMES: [&str; 7] = [
+    "join_with",
+    "lang",
+    "allow_invisible",
+    "ext",
+    "region",
+    "comment",
+    "lines",
+];
+
+/// A range of line numbers, e.g. `10..=20`, `10..21`, or `10..`.
+pub struct LineRange {
+    first: usize,
+    last: Option<usize>,
+}
+
+impl Parse for LineRange {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected a range of line numbers, e.g. `10..=20` or `10..`";
+
+        let number = |stream: ParseStream| {
+            stream
+                .parse::<LitInt>()
+                .and_then(|literal| literal.base10_parse::<usize>())
+                .map_err(|error| Error::new(error.span(), MESSAGE))
+        };
+
+        let first = number(stream)?;
+        let last = if stream.peek(Token![..=]) {
+            <Token![..=]>::parse(stream)?;
+            Some(number(stream)?)
+        } else {
+            let dots = <Token![..]>::parse(stream)
+                .map_err(|error| Error::new(error.span(), MESSAGE))?;
+            if stream.peek(LitInt) {
+                // NOTE: An exclusive end of `0` is an empty range either way
+                Some(number(stream)?.checked_sub(1).ok_or_else(|| {
+                    Error::new_spanned(dots, "Line range is empty")
+                })?)
+            } else {
+                None
+            }
+        };
+
+        Ok(Self { first, last })
+    }
+}
+
+/// The arguments of `trim_file!` and `trim_dir!`, i.e. a path literal followed
+/// by named parameters.
+pub struct Arguments {
+    path: LitStr,
+    join_with: Option<Parameter<Delimiter>>,
+    lang: Option<Parameter<Lang>>,
+    allow_invisible: Option<Parameter<Flag>>,
+    extension: Option<Parameter<LitStr>>,
+    region: Option<Parameter<LitStr>>,
+    comment: Option<Parameter<LitStr>>,
+    lines: Option<Parameter<LineRange>>,
+}
+
+impl Arguments {
+    fn parse_parameter(&mut self, stream: ParseStream) -> syn::Result<()> {
+        let identifier = Ident::parse(stream).map_err(|error| {
+            let message =
+                format!("Expected named parameter {}", listed(&NAMES));
+            Error::new(error.span(), message)
+        })?;
+
+        match identifier.to_string().as_str() {
+            "join_with" => {
+                let join_with = parse_value(stream, identifier)?;
+                if let Delimiter::MainSeparator = join_with.value {
+                    let message =
+                        "`MAIN_SEPARATOR` cannot be used with the content of \
+                         files";
+                    return Err(Error::new_spanned(join_with.name, message));
+                }
+                set_once(&mut self.join_with, join_with)
+            },
+            "lang" => {
+                set_once(&mut self.lang, parse_value(stream, identifier)?)
+            },
+            "allow_invisible" => set_once(
+                &mut self.allow_invisible,
+                parse_flag(stream, identifier)?,
+            ),
+            "ext" => {
+                set_once(&mut self.extension, parse_value(stream, identifier)?)
+            },
+            "region" => {
+                set_once(&mut self.region, parse_value(stream, identifier)?)
+            },
+            "comment" => {
+                set_once(&mut self.comment, parse_value(stream, identifier)?)
+            },
+            "lines" => {
+                set_once(&mut self.lines, parse_value(stream, identifier)?)
+            },
+            _ => {
+                let message =
+                    format!("Expected named parameter {}", listed(&NAMES));
+                Err(Error::new_spanned(identifier, message))
+            },
+        }
+    }
+
+    /// Returns the transformations to apply to the content of the files.
+    fn options(&self) -> Options {
+        Options {
+            join_with: self.join_with.as_ref().map(|join_with| {
+                transform::Delimiter::Bytes(join_with.value.to_bytes())
+            }),
+            lang: self.lang.as_ref().map(|lang| lang.value.0),
+            allow_invisible: enabled(&self.allow_invisible).is_some(),
+            ..Options::default()
+        }
+    }
+
+    /// Returns the path relative to the manifest directory of the crate the
+    /// macro is invoked in.
+    fn resolved(&self) -> syn::Result<PathBuf> {
+        let directory = env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+            let message = "`CARGO_MANIFEST_DIR` is not set, paths can only be \
+                           resolved when building with Cargo";
+            Error::new(self.path.span(), message)
+        })?;
+
+        Ok(Path::new(&directory).join(self.path.value()))
+    }
+}
+
+impl Parse for Arguments {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str = "Expected a string literal as a path";
+
+        let path = stream
+            .parse::<LitStr>()
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+        let mut arguments = Self {
+            path,
+            join_with: None,
+            lang: None,
+            allow_invisible: None,
+            extension: None,
+            region: None,
+            comment: None,
+            lines: None,
+        };
+
+        let mut results = Vec::new();
+
+        loop {
+            if stream.is_empty() {
+                break;
+            }
+
+            if let Err(error) = Comma::parse(stream) {
+                let message =
+                    "Expected `,` to follow or the end of the macro invocation";
+                results.push(Err(Error::new(error.span(), message)));
+                skip_to_comma(stream)?;
+                continue;
+            }
+
+            if stream.is_empty() {
+                break;
+            }
+
+            let result = arguments.parse_parameter(stream);
+            if result.is_err() {
+                skip_to_comma(stream)?;
+            }
+            results.push(result);
+        }
+
+        results.extend([
+            exclusive(&arguments.lang, &arguments.join_with),
+            exclusive(&arguments.region, &arguments.lines),
+        ]);
+        if let (Some(comment), None) = (&arguments.comment, &arguments.region) {
+            let message = "Named parameter `comment` can only be used with \
+                           `region`";
+            results.push(Err(Error::new_spanned(&comment.name, message)));
+        }
+        combined(results)?;
+
+        Ok(arguments)
+    }
+}
+
+/// Reads the file at `path`, and produces an item which makes the compiler
+/// track the file, so that the crate is rebuilt whenever it changes.
+fn read(path: &Path, span: Span) -> syn::Result<(Vec<u8>, TokenStream)> {
+    let display = path.display();
+    let content = fs::read(path).map_err(|error| {
+        Error::new(span, format!("Cannot read `{display}`: {error}"))
+    })?;
+
+    let Some(path) = path.to_str() else {
+        let message = format!("Path `{display}` is not valid UTF-8");
+        return Err(Error::new(span, message));
+    };
+    let tracked = quote! {
+        const _: &[u8] = ::core::include_bytes!(#path);
+    };
+
+    Ok((content, tracked))
+}
+
+fn trimmed(
+    path: &Path,
+    content: &[u8],
+    options: &Options,
+    span: Span,
+) -> syn::Result<String> {
+    let invalid = |message: &dyn Display| {
+        let message = format!("Cannot trim `{}`: {message}", path.display());
+        Error::new(span, message)
+    };
+    let trimmed =
+        options.trimmed(content).map_err(|message| invalid(&message))?;
+
+    String::from_utf8(trimmed).map_err(|error| invalid(&error.utf8_error()))
+}
+
+pub fn expand_file(arguments: Arguments) -> syn::Result<TokenStream> {
+    let span = arguments.path.span();
+    if let Some(extension) = &arguments.extension {
+        let message = "Named parameter `ext` can only be used with `trim_dir`";
+        return Err(Error::new_spanned(&extension.name, message));
+    }
+
+    let path = arguments.resolved()?;
+    let (content, tracked) = read(&path, span)?;
+
+    let content = match (&arguments.region, &arguments.lines) {
+        (Some(region), _) => {
+            let comment = arguments
+                .comment
+                .as_ref()
+                .map_or_else(|| String::from("//"), |comment| {
+                    comment.value.value()
+                });
+            let name = region.value.value();
+            select::region(&content, &name, &comment)
+                .map_err(|message| Error::new_spanned(&region.name, message))
+        },
+        (None, Some(lines)) => {
+            let LineRange { first, last } = lines.value;
+            select::lines(&content, first, last)
+                .map_err(|message| Error::new_spanned(&lines.name, message))
+        },
+        (None, None) => Ok(content),
+    }?;
+
+    let trimmed = trimmed(&path, &content, &arguments.options(), span)?;
+
+    Ok(quote! {
+        {
+            #tracked
+            #trimmed
+        }
+    })
+}
+
+pub fn expand_dir(arguments: Arguments) -> syn::Result<TokenStream> {
+    let span = arguments.path.span();
+    let selections = [
+        arguments.region.as_ref().map(|region| &region.name),
+        arguments.comment.as_ref().map(|comment| &comment.name),
+        arguments.lines.as_ref().map(|lines| &lines.name),
+    ];
+    if let Some(name) = selections.into_iter().flatten().next() {
+        let message = format!(
+            "Named parameter `{name}` can only be used with `trim_file`",
+        );
+        return Err(Error::new_spanned(name, message));
+    }
+
+    let directory = arguments.resolved()?;
+    let extension = arguments
+        .extension
+        .as_ref()
+        .map(|extension| extension.value.value());
+    let is_selected = |path: &Path| match &extension {
+        Some(extension) => {
+            path.extension().is_some_and(|found| found == extension.as_str())
+        },
+        None => true,
+    };
+
+    let files = files_in(&directory, is_selected).map_err(|(path, error)| {
+        let message = format!("Cannot read `{}`: {error}", path.display());
+        Error::new(span, message)
+    })?;
+
+    let options = arguments.options();
+    let mut entries = Vec::with_capacity(files.len());
+    let mut results = Vec::new();
+    for relative in files {
+        // NOTE: The keys are the same on every platform
+        let key = relative
+            .iter()
+            .map(|component| component.to_str())
+            .collect::<Option<Vec<_>>>()
+            .map(|components| components.join("/"));
+        let Some(key) = key else {
+            let path = relative.display();
+            let message = format!("Path `{path}` is not valid UTF-8");
+            results.push(Err(Error::new(span, message)));
+            continue;
+        };
+
+        let path = directory.join(&relative);
+        let entry = read(&path, span).and_then(|(content, tracked)| {
+            let trimmed = trimmed(&path, &content, &options, span)?;
+            Ok((key, trimmed, tracked))
+        });
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(error) => results.push(Err(error)),
+        }
+    }
+    combined(results)?;
+
+    entries.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+    let tracked = entries.iter().map(|(_, _, tracked)| tracked);
+    let pairs = entries.iter().map(|(key, trimmed, _)| {
+        quote! { (#key, #trimmed) }
+    });
+
+    Ok(quote! {
+        {
+            #(#tracked)*
+            const FILES: &[(&str, &str)] = &[#(#pairs),*];
+            FILES
+        }
+    })
+}
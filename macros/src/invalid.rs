@@ -0,0 +1,16 @@
+use syn::Error;
+use strim_core::invalid::Invalid;
+use super::spans::Spans;
+
+pub trait IntoError {
+    /// Converts the problem into an error which points at the offending part
+    /// of the literal, where `value` is the literal's (unescaped) value.
+    fn into_error(self, value: &[u8], spans: &Spans) -> Error;
+}
+
+impl IntoError for Invalid {
+    fn into_error(self, value: &[u8], spans: &Spans) -> Error {
+        let message = self.located(value);
+        Error::new(spans.subspan(self.range), message)
+    }
+}
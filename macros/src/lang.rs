@@ -0,0 +1,29 @@
+use syn::{
+    Error,
+    Ident,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use strim_core::transform;
+
+/// The value of `lang = ...`.
+#[derive(Clone)]
+pub struct Lang(pub transform::Lang);
+
+impl Parse for Lang {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either `graphql` or `regex` as a language";
+
+        let identifier = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+
+        match identifier.to_string().as_str() {
+            "graphql" => Ok(Self(transform::Lang::GraphQl)),
+            "regex" => Ok(Self(transform::Lang::Regex)),
+            _ => Err(Error::new_spanned(identifier, MESSAGE)),
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! The procedural macros of [`strim`](https://docs.rs/strim), which should be
+//! used through that crate.
+
+mod string;
+mod c_string;
+mod byte_string;
+mod invalid;
+mod spans;
+mod lang;
+mod decode;
+mod encode;
+mod lint;
+mod constraint;
+mod output;
+mod parameters;
+mod arguments;
+mod trim_all;
+mod file;
+mod vars;
+mod cfg;
+mod metadata;
+mod assert;
+
+use syn::{Error, Ident, Item, visit_mut::VisitMut, parse_macro_input};
+use quote::ToTokens;
+use proc_macro2::Span;
+use proc_macro::TokenStream;
+use strim_core::transform;
+use self::{
+    arguments::{Arguments, Preset},
+    encode::Encode,
+    lint::{Lint, Warnings},
+    output::platform_specific,
+    parameters::{Parameter, Parameters},
+    spans::Spans,
+    trim_all::{Options, Trimmer},
+    assert::Assertion,
+};
+
+/// [`trim!`] can be used on any string, byte-string, and C-string literals to
+/// remove all blank lines and trim each line's leading and trailing whitespace.
+///
+/// To remove whitespace, under the hood `trim` uses [`str::trim`] for strings,
+/// and [`<[u8]>::trim_ascii`][ta] for both byte-strings and C-strings.
+///
+/// When `trim` processes a byte-string or a C-string literal, it considers
+/// either newline characters (`\n`) or sequences of carriage return followed by
+/// a line feed (`\r\n`) as line boundaries.  (This also means that carriage
+/// return (`\r`) not immediately followed by a line feed (`\n`) is not
+/// considered a line break.)
+///
+/// In all scenarios, whether a line is blank is considered after it has been
+/// trimmed, that is, if a line contains whitespace only, then it will be
+/// considered as blank and therefore ignored.
+///
+/// # Example
+///
+/// ```
+/// # use strim::trim;
+/// let expected = r#"<a x="x"><b y="y"><c z="z">Hello, World!</c></b></a>"#;
+/// let actual = trim!(r#"
+///     <a x="x">
+///         <b y="y">
+///             <c z="z">
+///                 Hello, World!
+///             </c>
+///         </b>
+///     </a>
+/// "#);
+/// assert_eq!(actual, expected);
+/// ```
+///
+/// The `trim` macro also accepts a named parameter called `join_with` which can
+/// be used to specify the _delimiter_ with which the lines are joined together.
+/// If the input is a string literal, the delimiter is expected to be either a
+/// character or a string literal.  If the input is a byte-string or a C-string
+/// literal, the delimiter is expected to be either a byte literal for both, or
+/// a byte-string literal for the former and a C-string literal for the latter.
+///
+/// For C-strings, if the delimiter is a byte literal, that cannot be the
+/// nul-terminator.  `trim` produces a compile-time error if it is.  In case the
+/// delimiter is a C-string, its nul-terminator will be omitted when joining the
+/// trimmed lines together, i.e. the resulting C-string literal will have one
+/// nul-terminator at the end.
+///
+/// If the input is a string literal, the delimiter can also be
+/// `MAIN_SEPARATOR`, in which case the lines are joined together with the path
+/// separator of the target platform, i.e. `\` on Windows and `/` everywhere
+/// else.  As a proc-macro cannot know the target platform, `trim` produces an
+/// expression which selects one of the two literals with `cfg!(windows)`.
+///
+/// > **N.B.** Only non-blank lines are joined together, i.e. the delimiter will
+/// > only be inserted between two non-blank lines.  What is considered as blank
+/// > line is explained above.
+///
+/// # Example
+///
+/// ```
+/// # use strim::trim;
+/// let expected = "Alpha<br/>Beta and Gamma<br/>Delta, Epsilon, and Zeta";
+/// let actual = trim!(
+///     "Alpha
+///      Beta and Gamma
+///      Delta, Epsilon, and Zeta",
+///     join_with = "<br/>",
+/// );
+/// assert_eq!(actual, expected);
+/// ```
+///
+/// # Named Parameters
+///
+/// Besides `join_with`, `trim` accepts the following named parameters, which
+/// are explained in detail (with examples) in the [crate documentation][np]:
+///
+/// - `lang = graphql` or `lang = regex` minifies a string literal according to
+///   the rules of the language, instead of joining its lines.
+/// - `decode = hex`, `base64`, `base64_url`, or `pem("<label>")` decodes the
+///   trimmed lines into a byte-string.
+/// - `encode = utf16`, `utf16_nul`, or `utf32` encodes the trimmed string into
+///   an array of code units.
+/// - `as = str`, `c_str`, `bytes`, `os_str`, or `path` sets the kind of the
+///   produced literal (`os_str` and `path` cannot be used in const contexts).
+/// - `max_len = N`, `min_lines = N`, `ascii_only`, `forbid = "..."`, and
+///   `require_utf8` are constraints the produced literal has to meet.
+/// - `vars = { name = "...", ... }` substitutes the `{name}` placeholders of a
+///   string literal.
+/// - `cfg_lines` keeps the lines marked with `@cfg(<predicate>)` only if their
+///   predicates hold.
+/// - `allow_invisible` allows invisible characters, which are rejected by
+///   default.
+/// - `allow(...)` silences the listed warnings, e.g. `allow(unchanged)`.
+/// - `debug` reports a preview of the trimmed literal as a warning.
+///
+/// The defaults of some of them can be set in the `[package.metadata.strim]`
+/// and `[workspace.metadata.strim]` tables of the manifests.
+///
+/// [ta]: https://doc.rust-lang.org/std/primitive.slice.html#method.trim_ascii
+/// [np]: ../strim/index.html#named-parameters
+#[proc_macro]
+pub fn trim(stream: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(stream as Arguments);
+
+    match expanded(arguments, &[]) {
+        Ok(token_stream) => token_stream,
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// [`trim_utf16!`] is the same as [`trim!`] with `encode = utf16`, unless a
+/// different encoding is specified explicitly, therefore it only accepts string
+/// literals.
+///
+/// # Example
+///
+/// ```
+/// # use strim::trim_utf16;
+/// let expected = "Hello, World!".encode_utf16().collect::<Vec<_>>();
+/// let actual = trim_utf16!("
+///     Hello,
+///     World!
+/// ", join_with = ' ');
+/// assert_eq!(actual.as_slice(), expected);
+/// ```
+#[proc_macro]
+pub fn trim_utf16(stream: TokenStream) -> TokenStream {
+    let mut arguments = parse_macro_input!(stream as Arguments);
+
+    let span = arguments.span();
+    let Arguments::String { parameters, .. } = &mut arguments else {
+        let message = "Expected a string literal";
+        return Error::new(span, message).to_compile_error().into();
+    };
+
+    parameters.encode.get_or_insert_with(|| Parameter {
+        name: Ident::new("encode", Span::call_site()),
+        value: Encode(transform::Encode::Utf16),
+    });
+
+    match expanded(arguments, &[]) {
+        Ok(token_stream) => token_stream,
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The implementation of the macros defined by `define_trim!`, which is not
+/// meant to be used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn trim_with_defaults(stream: TokenStream) -> TokenStream {
+    let Preset(arguments) = parse_macro_input!(stream as Preset);
+
+    match expanded(arguments, &[]) {
+        Ok(token_stream) => token_stream,
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The implementation of `assert_trimmed_eq!`, which is not meant to be used
+/// directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn assert_trimmed(stream: TokenStream) -> TokenStream {
+    let assertion = parse_macro_input!(stream as Assertion);
+
+    // NOTE: The expected value is compared to the actual one, hence it is not
+    //       a mistake if it is left unchanged
+    match expanded(assertion.arguments.clone(), &[Lint::Unchanged]) {
+        Ok(expected) => assertion.expand(expected.into()).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// [`macro@trim_all`] trims the string, byte-string, and C-string literals of
+/// all the expressions in the item (e.g. a function, an `impl` block, a module,
+/// a constant, or a static) it is attached to, the same way [`trim!`] would.
+/// Doc comments, the arguments of attributes and of macro invocations, and the
+/// literals in patterns are left as they are.
+///
+/// The attribute accepts the following named parameters:
+///
+/// - `join_with` is the delimiter the lines are joined together with, which is
+///   either a character or a string literal (for byte-strings and C-strings its
+///   UTF-8 encoded bytes are used).  Unlike with `trim`, `MAIN_SEPARATOR`
+///   cannot be used, as the literals are replaced with literals.
+/// - `only = hashed` restricts the trimming to raw literals with at least one
+///   `#`, e.g. `r#"..."#`, while `only = multiline` restricts it to literals
+///   with more than one line.
+/// - `allow_invisible` is the same as it is for `trim`.
+///
+/// # Example
+///
+/// ```
+/// #[strim::trim_all(join_with = ' ', only = multiline)]
+/// mod queries {
+///     pub const USER: &str = "
+///         SELECT name
+///         FROM users
+///         WHERE id = ?
+///     ";
+///     pub const SEPARATOR: &str = ", ";
+/// }
+///
+/// assert_eq!(queries::USER, "SELECT name FROM users WHERE id = ?");
+/// assert_eq!(queries::SEPARATOR, ", ");
+/// ```
+#[proc_macro_attribute]
+pub fn trim_all(attribute: TokenStream, stream: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(attribute as Options);
+    let mut item = parse_macro_input!(stream as Item);
+
+    let mut trimmer = Trimmer::new(options);
+    trimmer.visit_item_mut(&mut item);
+
+    let mut token_stream = match trimmer.finish() {
+        Ok(()) => proc_macro2::TokenStream::new(),
+        Err(error) => error.to_compile_error(),
+    };
+    item.to_tokens(&mut token_stream);
+
+    token_stream.into()
+}
+
+/// [`trim_file!`] reads the file at the given path at compile time, and trims
+/// its content the same way [`trim!`] would trim a string literal, i.e. it
+/// expands to a `&'static str`.  The path is relative to the directory of the
+/// manifest (`Cargo.toml`) of the crate the macro is invoked in.
+///
+/// The macro accepts the named parameters `join_with` (either a character or a
+/// string literal), `lang`, and `allow_invisible`, which are the same as they
+/// are for `trim`.  The file is tracked by the compiler, so the crate is
+/// rebuilt whenever it changes.
+///
+/// # Example
+///
+/// ```ignore
+/// const USERS: &str = strim::trim_file!("queries/users.sql", join_with = ' ');
+/// ```
+///
+/// # Selection
+///
+/// Only a part of the file can be trimmed by using either of the following
+/// named parameters, which cannot be combined with each other:
+///
+/// - `region = "<name>"` selects the lines between a line of
+///   `// ANCHOR: <name>` and a line of `// ANCHOR_END: <name>`, leaving out the
+///   markers of any other regions in between.  The prefix of the markers can be
+///   changed with `comment = "..."`, e.g. `comment = "--"` for SQL, or
+///   `comment = "<!--"` for HTML (as only the first word after the marker is
+///   considered to be the name, the comment can be closed on the same line).
+/// - `lines = <range>` selects the lines of the given range, which are
+///   numbered from 1, e.g. `lines = 10..=20`, `lines = 10..21`, or
+///   `lines = 10..`.
+///
+/// `trim_file` produces a compile-time error if the region is not found or if
+/// it is not closed, or if the range is empty or it is out of bounds.
+///
+/// # Example
+///
+/// ```ignore
+/// const USERS: &str = strim::trim_file!(
+///     "queries/users.sql",
+///     region = "by_id",
+///     comment = "--",
+///     join_with = ' ',
+/// );
+/// ```
+#[proc_macro]
+pub fn trim_file(stream: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(stream as file::Arguments);
+
+    match file::expand_file(arguments) {
+        Ok(token_stream) => token_stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// [`trim_dir!`] reads all the files in the given directory (including the
+/// ones in its subdirectories) at compile time, and trims each one the same
+/// way [`trim_file!`] would.  It expands to a
+/// `&'static [(&'static str, &'static str)]` of the relative paths of the files
+/// (with `/` as the separator on every platform) and their trimmed content,
+/// sorted by the relative paths, so that it can be searched with
+/// `binary_search_by_key`.
+///
+/// Besides the named parameters of `trim_file`, it also accepts `ext`, which
+/// restricts the files to the ones with the given extension, e.g.
+/// `ext = "sql"`.  Every file is tracked by the compiler, but as procedural
+/// macros cannot track directories on stable, adding a new file only takes
+/// effect when the crate is rebuilt for another reason.
+///
+/// # Example
+///
+/// ```ignore
+/// static MIGRATIONS: &[(&str, &str)] =
+///     strim::trim_dir!("migrations", ext = "sql", join_with = ' ');
+///
+/// for (path, migration) in MIGRATIONS {
+///     println!("{path}: {migration}");
+/// }
+/// ```
+#[proc_macro]
+pub fn trim_dir(stream: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(stream as file::Arguments);
+
+    match file::expand_dir(arguments) {
+        Ok(token_stream) => token_stream.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Expands `arguments` (without the warnings of the `allowed` lints), or if
+/// `cfg_lines` is set, every variant of the literal, of which the one whose
+/// predicates hold is selected by `cfg!`.
+fn expanded(
+    arguments: Arguments,
+    allowed: &[Lint],
+) -> syn::Result<TokenStream> {
+    if !arguments.has_cfg_lines() {
+        return expand(arguments, allowed, None);
+    }
+
+    cfg::expanded(arguments, allowed, expand)
+}
+
+/// Expands `arguments` (without the warnings of the `allowed` lints), where the
+/// preview (if any) is only reported if `preview_cfg` holds.
+fn expand(
+    arguments: Arguments,
+    allowed: &[Lint],
+    preview_cfg: Option<String>,
+) -> syn::Result<TokenStream> {
+    let mut allowed_lints = arguments.allowed();
+    allowed_lints.extend(allowed);
+    let warnings = Warnings::new(allowed_lints, preview_cfg);
+    let value = arguments.value();
+    let spans = arguments.spans();
+
+    match &arguments {
+        Arguments::String { parameters, .. } => {
+            let options = parameters.options(
+                transform::Literal::String,
+                string::Delimiter::to_delimiter,
+            );
+            transformed(parameters, &options, &value, &spans, warnings)
+        },
+        Arguments::CString { parameters, .. } => {
+            let options = parameters.options(
+                transform::Literal::CString,
+                c_string::Delimiter::to_delimiter,
+            );
+            transformed(parameters, &options, &value, &spans, warnings)
+        },
+        Arguments::ByteString { parameters, .. } => {
+            let options = parameters.options(
+                transform::Literal::ByteString,
+                byte_string::Delimiter::to_delimiter,
+            );
+            transformed(parameters, &options, &value, &spans, warnings)
+        },
+    }
+}
+
+/// Produces the literal of `value` (i.e. of the value of the literal `spans`
+/// belong to) transformed with the `options` of the `parameters`, together
+/// with the `warnings` of the transformation.
+fn transformed<D>(
+    parameters: &Parameters<D>,
+    options: &transform::Options,
+    value: &[u8],
+    spans: &Spans,
+    mut warnings: Warnings,
+) -> syn::Result<TokenStream> {
+    let error_of = |error| parameters.error_of(error, value, spans);
+    let emit = |bytes: Vec<u8>| match options.encode {
+        // NOTE: Only strings can be encoded, which remain valid UTF-8
+        Some(encode) => {
+            Ok(encode::encoded(encode, &String::from_utf8_lossy(&bytes)))
+        },
+        None => output::emit(options.output(), bytes).map_err(|invalid| {
+            let message =
+                format!("Invalid trimmed literal: {}", invalid.message);
+            Error::new(spans.span(), message)
+        }),
+    };
+
+    let (output, token_stream) = match &options.join_with {
+        Some(transform::Delimiter::MainSeparator) => {
+            let [windows, others] = ["\\", "/"].map(|separator| {
+                let options = transform::Options {
+                    join_with: Some(separator.into()),
+                    ..options.clone()
+                };
+                options.transformed(value)
+            });
+            let (windows, others) = match (windows, others) {
+                (Ok(windows), Ok(others)) => (windows, others),
+                // NOTE: As the outputs only differ in the delimiter, the same
+                //       messages are only reported once
+                (
+                    Err(transform::Error::Output(mut messages)),
+                    Err(transform::Error::Output(others)),
+                ) => {
+                    for message in others {
+                        if !messages.contains(&message) {
+                            messages.push(message);
+                        }
+                    }
+                    return Err(error_of(transform::Error::Output(messages)));
+                },
+                (Err(error), _) | (_, Err(error)) => {
+                    return Err(error_of(error));
+                },
+            };
+            let token_stream =
+                platform_specific(emit(windows)?, emit(others.clone())?);
+
+            (others, token_stream)
+        },
+        _ => {
+            let output = options.transformed(value).map_err(error_of)?;
+            let token_stream = emit(output.clone())?;
+
+            (output, token_stream)
+        },
+    };
+
+    // NOTE: If the output of one platform is unchanged, so is the other's, and
+    //       they only differ in the delimiter
+    let lints = options.lints(value, &output);
+    warnings.check(lints, spans, &parameters.join_with);
+    warnings.preview(&parameters.debug, || options.preview(value, &output));
+
+    let token_stream = match &parameters.vars {
+        Some(vars) => vars.value.tracked(token_stream),
+        None => token_stream,
+    };
+
+    Ok(warnings.emit(token_stream))
+}
@@ -0,0 +1,195 @@
+use std::ops::Range;
+use syn::{
+    Error,
+    Ident,
+    parenthesized,
+    token::{Comma, Paren},
+    punctuated::Punctuated,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro2::Span;
+use proc_macro::{
+    Group,
+    Punct,
+    Literal,
+    Spacing,
+    Delimiter,
+    TokenTree,
+    TokenStream,
+};
+use strim_core::lint::NAMES;
+use super::{
+    spans::Spans,
+    parameters::{Flag, Parameter, enabled, listed},
+};
+
+pub use strim_core::lint::Lint;
+
+/// Parses the name of a lint.
+pub fn parse_lint(stream: ParseStream) -> syn::Result<Lint> {
+    let identifier = Ident::parse(stream)?;
+
+    Lint::named(&identifier.to_string()).ok_or_else(|| {
+        let message = format!("Expected either {} as a lint", listed(&NAMES));
+        Error::new_spanned(identifier, message)
+    })
+}
+
+/// The lints listed in `allow(...)`.
+#[derive(Clone)]
+pub struct Allowed {
+    pub lints: Vec<Lint>,
+}
+
+impl Parse for Allowed {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        if !stream.peek(Paren) {
+            let message =
+                "Expected `allow` to be followed by a parenthesized list of \
+                 lints, e.g. `allow(unchanged)`";
+            return Err(Error::new(stream.span(), message));
+        }
+
+        let content;
+        parenthesized!(content in stream);
+
+        let lints = Punctuated::<Lint, Comma>::parse_terminated_with(
+            &content,
+            parse_lint,
+        )?;
+
+        Ok(Self { lints: lints.into_iter().collect() })
+    }
+}
+
+/// The name of the constant which reports the preview requested by `debug`.
+const PREVIEW: &str = "trim_preview";
+
+/// Produces the definition of a deprecated constant called `name`.
+fn deprecated(name: &str, note: &str) -> TokenStream {
+    let definition = format!(
+        "#[deprecated(note = {})] \
+         #[allow(non_upper_case_globals)] \
+         const {name}: () = ();",
+        Literal::string(note),
+    );
+
+    definition.parse().expect("Invalid definition for a warning")
+}
+
+/// Produces a statement which uses the constant called `name` at `span`.
+fn used(name: &str, span: Span) -> TokenStream {
+    let span = span.unwrap();
+    let [binding, wildcard, name] =
+        ["let", "_", name].map(|name| proc_macro::Ident::new(name, span));
+
+    TokenStream::from_iter([
+        TokenTree::from(binding),
+        TokenTree::from(wildcard),
+        TokenTree::from(Punct::new('=', Spacing::Alone)),
+        TokenTree::from(name),
+        TokenTree::from(Punct::new(';', Spacing::Alone)),
+    ])
+}
+
+/// Collects the warnings of an invocation, which are not allowed.
+pub struct Warnings {
+    allowed: Vec<Lint>,
+    warnings: Vec<(Lint, Span)>,
+    preview: Option<(Span, String)>,
+    /// The `cfg` predicate which has to hold for the preview to be reported,
+    /// i.e. the one which selects the variant of a literal with `cfg_lines`
+    preview_cfg: Option<String>,
+}
+
+impl Warnings {
+    pub fn new(allowed: Vec<Lint>, preview_cfg: Option<String>) -> Self {
+        Self { allowed, warnings: Vec::new(), preview: None, preview_cfg }
+    }
+
+    /// Reports the `preview` as a warning if `debug` is enabled.
+    pub fn preview(
+        &mut self,
+        debug: &Option<Parameter<Flag>>,
+        preview: impl FnOnce() -> String,
+    ) {
+        if let Some(debug) = enabled(debug) {
+            self.preview = Some((debug.name.span(), preview()));
+        }
+    }
+
+    pub fn warn(&mut self, lint: Lint, span: Span) {
+        if !self.allowed.contains(&lint) {
+            self.warnings.push((lint, span));
+        }
+    }
+
+    /// Reports the `lints` of the transformation of the literal `spans`
+    /// belong to, where the ones which are not about a part of the literal are
+    /// reported at `join_with` (if it is about the delimiter) or at the
+    /// literal.
+    pub fn check<D>(
+        &mut self,
+        lints: Vec<(Lint, Option<Range<usize>>)>,
+        spans: &Spans,
+        join_with: &Option<Parameter<D>>,
+    ) {
+        for (lint, range) in lints {
+            let span = match (range, lint, join_with) {
+                (Some(range), _, _) => spans.subspan(range),
+                (None, Lint::NeedlessJoinWith, Some(join_with)) => {
+                    join_with.name.span()
+                },
+                (None, _, _) => spans.span(),
+            };
+            self.warn(lint, span);
+        }
+    }
+
+    /// Produces `expression` as it is if there are no warnings, otherwise it
+    /// puts it in a block with a deprecated constant per lint and their uses.
+    /// (Deprecation warnings are the only ones a procedural macro can emit on
+    /// stable.)
+    pub fn emit(self, expression: TokenStream) -> TokenStream {
+        if self.warnings.is_empty() && self.preview.is_none() {
+            return expression;
+        }
+
+        let mut block = TokenStream::new();
+
+        for lint in Lint::ALL {
+            if self.warnings.iter().any(|(warned, _)| *warned == lint) {
+                let note = format!(
+                    "{} (silence with `allow({})`)",
+                    lint.message(),
+                    lint.name(),
+                );
+                block.extend(deprecated(lint.name(), &note));
+            }
+        }
+
+        for (lint, span) in self.warnings {
+            block.extend(used(lint.name(), span));
+        }
+
+        if let Some((span, preview)) = self.preview {
+            let attribute: TokenStream = match &self.preview_cfg {
+                Some(predicate) => format!("#[cfg({predicate})]")
+                    .parse()
+                    .expect("Invalid attribute for a preview"),
+                None => TokenStream::new(),
+            };
+            block.extend(attribute.clone());
+            block.extend(deprecated(PREVIEW, &preview));
+            block.extend(attribute);
+            block.extend(used(PREVIEW, span));
+        }
+
+        block.extend(expression);
+
+        TokenTree::from(Group::new(Delimiter::Brace, block)).into()
+    }
+}
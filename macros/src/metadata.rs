@@ -0,0 +1,388 @@
+use std::{
+    env,
+    fs,
+    io,
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CString,
+    path::{Path, PathBuf},
+};
+use syn::{Error, parse::Parser};
+use quote::quote;
+use proc_macro2::{Span, Ident, Literal, TokenStream};
+use toml::{Table, Value};
+use super::{lint::parse_lint, parameters::listed};
+
+const KEYS: [&str; 7] = [
+    "join_with",
+    "allow",
+    "allow_invisible",
+    "max_len",
+    "min_lines",
+    "ascii_only",
+    "forbid",
+];
+
+/// The kind of the literal the defaults are for.
+#[derive(Clone, Copy)]
+pub enum Kind {
+    String,
+    CString,
+    ByteString,
+}
+
+/// The defaults of the named parameters, set by the `[package.metadata.strim]`
+/// and the `[workspace.metadata.strim]` tables of the manifests.
+#[derive(Clone, Debug, Default)]
+pub struct Metadata {
+    join_with: Option<String>,
+    allow: Option<Vec<String>>,
+    allow_invisible: Option<bool>,
+    max_len: Option<u64>,
+    min_lines: Option<u64>,
+    ascii_only: Option<bool>,
+    forbid: Option<String>,
+}
+
+/// Reads the manifest at `path`, if there is one.
+fn read(path: &Path) -> Result<Option<Table>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(None);
+        },
+        Err(error) => return Err(error.to_string()),
+    };
+
+    content
+        .parse::<Table>()
+        .map(Some)
+        .map_err(|error| error.message().to_string())
+}
+
+/// Returns the `[<section>.metadata.strim]` table of `manifest` (if any).
+fn strim_table<'a>(
+    manifest: &'a Table,
+    section: &str,
+) -> Result<Option<&'a Table>, String> {
+    let mut table = manifest;
+    for key in [section, "metadata", "strim"] {
+        match table.get(key) {
+            Some(Value::Table(inner)) => table = inner,
+            Some(_) => return Err(format!("`{key}` is expected to be a table")),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(table))
+}
+
+/// Returns the path of the root manifest of the workspace the package in
+/// `directory` belongs to (if any).
+fn workspace_of(directory: &Path, manifest: &Table) -> Option<PathBuf> {
+    if manifest.contains_key("workspace") {
+        return Some(directory.join("Cargo.toml"));
+    }
+
+    let root = manifest
+        .get("package")
+        .and_then(|package| package.get("workspace"))
+        .and_then(Value::as_str);
+    if let Some(root) = root {
+        return Some(directory.join(root).join("Cargo.toml"));
+    }
+
+    // NOTE: Cargo looks for the closest manifest with a `[workspace]` table,
+    //       which does not exclude the package
+    directory.ancestors().skip(1).find_map(|ancestor| {
+        let path = ancestor.join("Cargo.toml");
+        let manifest = read(&path).ok()??;
+        let relative = directory.strip_prefix(ancestor).ok()?;
+        let is_excluded = manifest
+            .get("workspace")?
+            .get("exclude")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .any(|excluded| relative.starts_with(excluded));
+
+        (!is_excluded).then_some(path)
+    })
+}
+
+thread_local! {
+    /// The metadata already read, by the directories of the packages.
+    static LOADED: RefCell<HashMap<PathBuf, Result<Metadata, String>>> =
+        RefCell::default();
+}
+
+impl Metadata {
+    /// Reads the defaults of the package the macro is invoked in, where the
+    /// ones of `[package.metadata.strim]` override the ones of
+    /// `[workspace.metadata.strim]`.  The manifests are only read once per
+    /// package, and a missing one (e.g. in a build not driven by Cargo) is the
+    /// same as one without defaults.
+    pub fn load() -> syn::Result<Self> {
+        let Some(directory) = env::var_os("CARGO_MANIFEST_DIR") else {
+            return Ok(Self::default());
+        };
+
+        LOADED
+            .with(|loaded| {
+                loaded
+                    .borrow_mut()
+                    .entry(PathBuf::from(directory))
+                    .or_insert_with_key(|directory| {
+                        Self::from_manifests(directory)
+                    })
+                    .clone()
+            })
+            .map_err(|message| Error::new(Span::call_site(), message))
+    }
+
+    /// Reads the defaults of the package in `directory`.
+    fn from_manifests(directory: &Path) -> Result<Self, String> {
+        let invalid = |path: &Path, message: String| {
+            format!("Invalid metadata in `{}`: {message}", path.display())
+        };
+
+        let path = directory.join("Cargo.toml");
+        let manifest = read(&path).map_err(|message| invalid(&path, message))?;
+        let Some(manifest) = manifest else {
+            return Ok(Self::default());
+        };
+        let package = strim_table(&manifest, "package")
+            .and_then(|table| table.map(Self::from_table).transpose())
+            .map_err(|message| invalid(&path, message))?
+            .unwrap_or_default();
+
+        let Some(path) = workspace_of(directory, &manifest) else {
+            return Ok(package);
+        };
+        let workspace = read(&path)
+            .and_then(|manifest| match manifest {
+                Some(manifest) => strim_table(&manifest, "workspace")?
+                    .map(Self::from_table)
+                    .transpose(),
+                None => Ok(None),
+            })
+            .map_err(|message| invalid(&path, message))?
+            .unwrap_or_default();
+
+        Ok(package.or(workspace))
+    }
+
+    fn from_table(table: &Table) -> Result<Self, String> {
+        let mut metadata = Self::default();
+        let expected = |key: &str, value: &str| {
+            Err(format!("`{key}` is expected to be {value}"))
+        };
+
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("join_with", Value::String(join_with)) => {
+                    metadata.join_with = Some(join_with.clone());
+                },
+                ("allow", Value::Array(lints)) => {
+                    let mut names = Vec::with_capacity(lints.len());
+                    for lint in lints {
+                        let Some(name) = lint.as_str() else {
+                            return expected(key, "an array of lint names");
+                        };
+                        parse_lint
+                            .parse_str(name)
+                            .map_err(|error| error.to_string())?;
+                        names.push(name.to_string());
+                    }
+                    metadata.allow = Some(names);
+                },
+                ("allow_invisible", Value::Boolean(allow_invisible)) => {
+                    metadata.allow_invisible = Some(*allow_invisible);
+                },
+                ("max_len" | "min_lines", Value::Integer(integer)) => {
+                    let Ok(integer) = u64::try_from(*integer) else {
+                        return expected(key, "a non-negative integer");
+                    };
+                    match key.as_str() {
+                        "max_len" => metadata.max_len = Some(integer),
+                        _ => metadata.min_lines = Some(integer),
+                    }
+                },
+                ("ascii_only", Value::Boolean(ascii_only)) => {
+                    metadata.ascii_only = Some(*ascii_only);
+                },
+                ("forbid", Value::String(forbid)) if !forbid.is_empty() => {
+                    metadata.forbid = Some(forbid.clone());
+                },
+                ("join_with", _) => return expected(key, "a string"),
+                ("allow", _) => return expected(key, "an array of lint names"),
+                ("allow_invisible" | "ascii_only", _) => {
+                    return expected(key, "a boolean");
+                },
+                ("max_len" | "min_lines", _) => {
+                    return expected(key, "a non-negative integer");
+                },
+                ("forbid", _) => return expected(key, "a non-empty string"),
+                _ => {
+                    return Err(format!(
+                        "Unknown key `{key}`, expected {}",
+                        listed(&KEYS),
+                    ));
+                },
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// Returns the metadata with the keys not set taken from `defaults`.
+    fn or(self, defaults: Self) -> Self {
+        Self {
+            join_with: self.join_with.or(defaults.join_with),
+            allow: self.allow.or(defaults.allow),
+            allow_invisible: self.allow_invisible.or(defaults.allow_invisible),
+            max_len: self.max_len.or(defaults.max_len),
+            min_lines: self.min_lines.or(defaults.min_lines),
+            ascii_only: self.ascii_only.or(defaults.ascii_only),
+            forbid: self.forbid.or(defaults.forbid),
+        }
+    }
+
+    /// Returns the named parameters of the defaults for a literal of `kind`,
+    /// separated by commas.
+    pub fn to_parameters(&self, kind: Kind) -> syn::Result<TokenStream> {
+        let mut parameters = Vec::new();
+
+        if let Some(join_with) = &self.join_with {
+            let join_with = match kind {
+                Kind::String => Literal::string(join_with),
+                Kind::ByteString => Literal::byte_string(join_with.as_bytes()),
+                Kind::CString => {
+                    let join_with =
+                        CString::new(join_with.as_str()).map_err(|_| {
+                            let message = "Invalid metadata: `join_with` \
+                                           cannot contain a nul character";
+                            Error::new(Span::call_site(), message)
+                        })?;
+                    Literal::c_string(&join_with)
+                },
+            };
+            parameters.push(quote!(join_with = #join_with));
+        }
+        if let Some(allow) = &self.allow {
+            let lints =
+                allow.iter().map(|lint| Ident::new(lint, Span::call_site()));
+            parameters.push(quote!(allow(#(#lints),*)));
+        }
+        if let Some(allow_invisible) = self.allow_invisible {
+            parameters.push(quote!(allow_invisible = #allow_invisible));
+        }
+        if let Some(max_len) = self.max_len {
+            let max_len = Literal::u64_unsuffixed(max_len);
+            parameters.push(quote!(max_len = #max_len));
+        }
+        if let Some(min_lines) = self.min_lines {
+            let min_lines = Literal::u64_unsuffixed(min_lines);
+            parameters.push(quote!(min_lines = #min_lines));
+        }
+        if let Some(ascii_only) = self.ascii_only {
+            parameters.push(quote!(ascii_only = #ascii_only));
+        }
+        if let Some(forbid) = &self.forbid {
+            parameters.push(quote!(forbid = #forbid));
+        }
+
+        Ok(quote!(#(#parameters),*))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(table: &str) -> Result<Metadata, String> {
+        Metadata::from_table(&table.parse::<Table>().unwrap())
+    }
+
+    #[test]
+    fn tables() {
+        let manifest = "[package.metadata.strim]\nmax_len = 8"
+            .parse::<Table>()
+            .unwrap();
+        assert!(strim_table(&manifest, "package").unwrap().is_some());
+        assert!(strim_table(&manifest, "workspace").unwrap().is_none());
+
+        let manifest = "package = 1".parse::<Table>().unwrap();
+        assert_eq!(
+            strim_table(&manifest, "package").unwrap_err(),
+            "`package` is expected to be a table",
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        let metadata = metadata(
+            "join_with = ' '\n\
+             allow = ['unchanged', 'trailing_backslash']\n\
+             max_len = 80\n\
+             ascii_only = true",
+        )
+        .unwrap();
+
+        assert_eq!(
+            metadata.to_parameters(Kind::String).unwrap().to_string(),
+            quote! {
+                join_with = " ",
+                allow(unchanged, trailing_backslash),
+                max_len = 80,
+                ascii_only = true
+            }
+            .to_string(),
+        );
+        assert!(metadata
+            .to_parameters(Kind::ByteString)
+            .unwrap()
+            .to_string()
+            .starts_with("join_with = b\" \""));
+        assert!(Metadata::default()
+            .to_parameters(Kind::String)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn overrides() {
+        let package = metadata("max_len = 8").unwrap();
+        let workspace = metadata("max_len = 16\nmin_lines = 2").unwrap();
+        let metadata = package.or(workspace);
+
+        assert_eq!(metadata.max_len, Some(8));
+        assert_eq!(metadata.min_lines, Some(2));
+    }
+
+    #[test]
+    fn missing() {
+        let directory = env::temp_dir().join("strim-missing-manifest");
+        let metadata = Metadata::from_manifests(&directory).unwrap();
+        assert!(metadata.to_parameters(Kind::String).unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            metadata("max_len = -1").unwrap_err(),
+            "`max_len` is expected to be a non-negative integer",
+        );
+        assert_eq!(
+            metadata("allow = 'unchanged'").unwrap_err(),
+            "`allow` is expected to be an array of lint names",
+        );
+        assert_eq!(
+            metadata("forbid = ''").unwrap_err(),
+            "`forbid` is expected to be a non-empty string",
+        );
+        assert!(metadata("allow = ['unknown']").is_err());
+        assert!(metadata("trim = true").unwrap_err().starts_with("Unknown"));
+    }
+}
@@ -0,0 +1,140 @@
+use std::ffi::{CStr, CString};
+use syn::{
+    Error,
+    Ident,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro::{
+    Group,
+    Literal,
+    Delimiter,
+    TokenTree,
+    TokenStream,
+};
+use strim_core::{invalid::Invalid, transform};
+
+/// The value of `as = ...`.
+#[derive(Clone)]
+pub struct Output(pub transform::Output);
+
+/// Produces an expression which evaluates to a `&'static CStr` without relying
+/// on C-string literals, i.e. it passes a nul-terminated byte-string literal to
+/// `CStr::from_bytes_with_nul_unchecked`.
+fn unchecked_c_string_of(c_string: &CStr) -> TokenStream {
+    let byte_string = Literal::byte_string(c_string.to_bytes_with_nul());
+    let expression = format!(
+        "unsafe {{ ::core::ffi::CStr::from_bytes_with_nul_unchecked({}) }}",
+        byte_string,
+    );
+
+    expression.parse().expect("Invalid expression for a C-string")
+}
+
+// NOTE: `clippy` is not aware of the `rustversion` gating
+#[rustversion::since(1.79.0)]
+#[allow(clippy::incompatible_msrv)]
+fn c_string_of(c_string: &CStr) -> TokenStream {
+    if cfg!(feature = "c-str-fallback") {
+        return unchecked_c_string_of(c_string);
+    }
+
+    TokenTree::from(Literal::c_string(c_string)).into()
+}
+
+#[rustversion::before(1.79.0)]
+fn c_string_of(c_string: &CStr) -> TokenStream {
+    unchecked_c_string_of(c_string)
+}
+
+/// Produces an expression which passes the string literal of `string` to the
+/// `constructor` function.
+fn constructed(constructor: &str, string: &str) -> TokenStream {
+    let expression =
+        format!("{constructor}({})", Literal::string(string));
+
+    expression.parse().expect("Invalid expression for a constructor")
+}
+
+/// Produces an `if` expression which evaluates to `windows` if the target is
+/// Windows, and to `others` otherwise.
+pub fn platform_specific(
+    windows: TokenStream,
+    others: TokenStream,
+) -> TokenStream {
+    let [condition, otherwise] = ["if ::core::cfg!(windows)", "else"]
+        .map(|code| code.parse().expect("Invalid code"));
+    let [windows, others] = [windows, others].map(|branch| {
+        TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace, branch)))
+    });
+
+    TokenStream::from_iter([condition, windows, otherwise, others])
+}
+
+/// Describes the nul character at `index`.
+fn nul_at(index: usize) -> Invalid {
+    Invalid::new(index..index + 1, "C-string cannot contain a nul character")
+}
+
+/// Returns `bytes` as a string, if they are valid UTF-8.
+fn string_of(bytes: Vec<u8>) -> Result<String, Invalid> {
+    String::from_utf8(bytes).map_err(|error| {
+        let error = error.utf8_error();
+        let start = error.valid_up_to();
+        let end = start + error.error_len().unwrap_or(1);
+        Invalid::new(start..end, "String cannot contain invalid UTF-8")
+    })
+}
+
+/// Produces the literal of `output` from `bytes`, or describes why they
+/// cannot be its value.  (The output has been checked already by the
+/// transformations, so the latter is not expected.)
+pub fn emit(
+    output: transform::Output,
+    bytes: Vec<u8>,
+) -> Result<TokenStream, Invalid> {
+    let expression = match output {
+        transform::Output::String => {
+            let string = string_of(bytes)?;
+            TokenTree::from(Literal::string(&string)).into()
+        },
+        transform::Output::CString => {
+            let c_string = CString::new(bytes)
+                .map_err(|error| nul_at(error.nul_position()))?;
+            c_string_of(&c_string)
+        },
+        transform::Output::ByteString => {
+            TokenTree::from(Literal::byte_string(&bytes)).into()
+        },
+        transform::Output::OsString => {
+            constructed("::std::ffi::OsStr::new", &string_of(bytes)?)
+        },
+        transform::Output::Path => {
+            constructed("::std::path::Path::new", &string_of(bytes)?)
+        },
+    };
+
+    Ok(expression)
+}
+
+impl Parse for Output {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either `str`, `c_str`, `bytes`, `os_str`, or `path` as \
+             an output";
+
+        let identifier = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+
+        match identifier.to_string().as_str() {
+            "str" => Ok(Self(transform::Output::String)),
+            "c_str" => Ok(Self(transform::Output::CString)),
+            "bytes" => Ok(Self(transform::Output::ByteString)),
+            "os_str" => Ok(Self(transform::Output::OsString)),
+            "path" => Ok(Self(transform::Output::Path)),
+            _ => Err(Error::new_spanned(identifier, MESSAGE)),
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use syn::{
+    Error,
+    Ident,
+    LitBool,
+    ext::IdentExt,
+    token::{Eq, Comma},
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro2::TokenTree;
+use strim_core::transform;
+use super::{
+    invalid::IntoError,
+    spans::Spans,
+    lang::Lang,
+    decode::Decode,
+    encode::Encode,
+    output::Output,
+    lint::{Lint, Allowed},
+    constraint::Constraints,
+    vars::Vars,
+};
+
+const NAMES: [&str; 15] = [
+    "join_with",
+    "lang",
+    "decode",
+    "encode",
+    "as",
+    "allow",
+    "allow_invisible",
+    "max_len",
+    "min_lines",
+    "ascii_only",
+    "forbid",
+    "require_utf8",
+    "debug",
+    "vars",
+    "cfg_lines",
+];
+
+#[derive(Clone)]
+pub struct Parameter<T> {
+    pub name: Ident,
+    pub value: T,
+}
+
+#[derive(Clone)]
+pub struct Parameters<D> {
+    pub join_with: Option<Parameter<D>>,
+    pub lang: Option<Parameter<Lang>>,
+    pub decode: Option<Parameter<Decode>>,
+    pub encode: Option<Parameter<Encode>>,
+    pub output: Option<Parameter<Output>>,
+    pub allow: Option<Parameter<Allowed>>,
+    pub allow_invisible: Option<Parameter<Flag>>,
+    pub constraints: Constraints,
+    pub debug: Option<Parameter<Flag>>,
+    pub vars: Option<Parameter<Vars>>,
+    pub cfg_lines: Option<Parameter<Flag>>,
+}
+
+/// A boolean value of a named parameter.
+#[derive(Clone)]
+pub struct Flag(pub bool);
+
+/// Returns `flag` if it is set to `true`.
+pub fn enabled(flag: &Option<Parameter<Flag>>) -> Option<&Parameter<Flag>> {
+    flag.as_ref().filter(|flag| flag.value.0)
+}
+
+impl Parse for Flag {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str = "Expected either `true` or `false`";
+
+        LitBool::parse(stream)
+            .map(|literal| Self(literal.value))
+            .map_err(|error| Error::new(error.span(), MESSAGE))
+    }
+}
+
+impl<D> Parameters<D> {
+    /// Returns the parameters with the ones not set taken from `defaults`.
+    ///
+    /// As the delimiter of `defaults` applies to every literal, including the
+    /// ones with a single line, `needless_join_with` is allowed for it.
+    pub fn or(self, defaults: Self) -> Self {
+        let mut allow = self.allow.or(defaults.allow);
+        if let (None, Some(join_with)) = (&self.join_with, &defaults.join_with)
+        {
+            let allow = allow.get_or_insert_with(|| Parameter {
+                name: Ident::new("allow", join_with.name.span()),
+                value: Allowed { lints: Vec::new() },
+            });
+            allow.value.lints.push(Lint::NeedlessJoinWith);
+        }
+
+        Self {
+            join_with: self.join_with.or(defaults.join_with),
+            lang: self.lang.or(defaults.lang),
+            decode: self.decode.or(defaults.decode),
+            encode: self.encode.or(defaults.encode),
+            output: self.output.or(defaults.output),
+            allow,
+            allow_invisible: self.allow_invisible.or(defaults.allow_invisible),
+            constraints: self.constraints.or(defaults.constraints),
+            debug: self.debug.or(defaults.debug),
+            vars: self.vars.or(defaults.vars),
+            cfg_lines: self.cfg_lines.or(defaults.cfg_lines),
+        }
+    }
+
+    /// Returns the name of the named parameter called `name` (where `as` is
+    /// the one of `output`), if it is set.
+    pub fn name_of(&self, name: &str) -> Option<&Ident> {
+        fn name_of<T>(parameter: &Option<Parameter<T>>) -> Option<&Ident> {
+            parameter.as_ref().map(|parameter| &parameter.name)
+        }
+
+        match name {
+            "join_with" => name_of(&self.join_with),
+            "lang" => name_of(&self.lang),
+            "decode" => name_of(&self.decode),
+            "encode" => name_of(&self.encode),
+            "as" => name_of(&self.output),
+            "allow" => name_of(&self.allow),
+            "allow_invisible" => name_of(&self.allow_invisible),
+            "max_len" => name_of(&self.constraints.max_len),
+            "min_lines" => name_of(&self.constraints.min_lines),
+            "ascii_only" => name_of(&self.constraints.ascii_only),
+            "forbid" => name_of(&self.constraints.forbid),
+            "require_utf8" => name_of(&self.constraints.require_utf8),
+            "debug" => name_of(&self.debug),
+            "vars" => name_of(&self.vars),
+            "cfg_lines" => name_of(&self.cfg_lines),
+            _ => None,
+        }
+    }
+
+    /// Returns the transformations of the parameters for the kind of
+    /// `literal`, where `to_delimiter` converts the delimiter.  (As the
+    /// variants of a literal with `cfg_lines` are transformed one by one, the
+    /// lines are not selected by the transformations.)
+    pub fn options(
+        &self,
+        literal: transform::Literal,
+        to_delimiter: impl FnOnce(&D) -> transform::Delimiter,
+    ) -> transform::Options {
+        transform::Options {
+            literal,
+            join_with: self
+                .join_with
+                .as_ref()
+                .map(|join_with| to_delimiter(&join_with.value)),
+            lang: self.lang.as_ref().map(|lang| lang.value.0),
+            decode: self.decode.as_ref().map(|decode| decode.value.0.clone()),
+            encode: self.encode.as_ref().map(|encode| encode.value.0),
+            output: self.output.as_ref().map(|output| output.value.0),
+            constraints: self.constraints.to_constraints(),
+            vars: self.vars.as_ref().map(|vars| vars.value.to_vars()),
+            cfg_lines: None,
+            allow_invisible: enabled(&self.allow_invisible).is_some(),
+        }
+    }
+
+    /// Turns the `error` of the transformation of `value` (i.e. the value of
+    /// the literal `spans` belong to) into an error at the part of the literal
+    /// or at the named parameter it is about.
+    pub fn error_of(
+        &self,
+        error: transform::Error,
+        value: &[u8],
+        spans: &Spans,
+    ) -> Error {
+        let at = |name: &str| {
+            self.name_of(name).map_or_else(|| spans.span(), Ident::span)
+        };
+
+        match error {
+            transform::Error::Conflict(conflict) => {
+                let message = format!(
+                    "Named parameter {}",
+                    conflict.describe(|name| format!("`{name}`")),
+                );
+                Error::new(at(conflict.name()), message)
+            },
+            transform::Error::Option { name, message } => {
+                Error::new(at(name), message)
+            },
+            transform::Error::Input(invalid) => {
+                invalid.into_error(value, spans)
+            },
+            transform::Error::Output(messages) => {
+                let mut errors = messages
+                    .into_iter()
+                    .map(|message| Error::new(spans.span(), message));
+                let mut error = errors
+                    .next()
+                    .expect("Invalid output without messages");
+                errors.for_each(|other| error.combine(other));
+                error
+            },
+        }
+    }
+
+    /// Checks whether the parameters which are set can be combined for the
+    /// kind of `literal` (whose value is `value` and whose spans are `spans`),
+    /// and whether the delimiter converted by `to_delimiter` is valid.
+    pub fn check(
+        &self,
+        literal: transform::Literal,
+        to_delimiter: impl FnOnce(&D) -> transform::Delimiter,
+        value: &[u8],
+        spans: &Spans,
+    ) -> syn::Result<()> {
+        let options = self.options(literal, to_delimiter);
+
+        combined(
+            options
+                .errors()
+                .into_iter()
+                .map(|error| Err(self.error_of(error, value, spans))),
+        )
+    }
+}
+
+pub fn parse_value<T>(
+    stream: ParseStream,
+    identifier: Ident,
+) -> syn::Result<Parameter<T>>
+where
+    T: Parse,
+{
+    if stream.is_empty() {
+        let message = "Expected `=` to follow";
+        return Err(Error::new(identifier.span(), message));
+    }
+
+    Eq::parse(stream).map_err(|error| {
+        let message =
+            format!("Expected `{identifier}` to be followed by a `=`");
+        Error::new(error.span(), message)
+    })?;
+
+    let value = T::parse(stream)?;
+
+    Ok(Parameter { name: identifier, value })
+}
+
+/// Parses the value of a flag, which can be omitted, in which case it is
+/// `true`.
+pub fn parse_flag(
+    stream: ParseStream,
+    identifier: Ident,
+) -> syn::Result<Parameter<Flag>> {
+    if stream.peek(Eq) {
+        return parse_value(stream, identifier);
+    }
+
+    Ok(Parameter { name: identifier, value: Flag(true) })
+}
+
+pub fn listed(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => format!("`{name}`"),
+        [names @ .., last] => {
+            let names: Vec<_> =
+                names.iter().map(|name| format!("`{name}`")).collect();
+            format!("{}, or `{last}`", names.join(", "))
+        },
+    }
+}
+
+/// Returns the number of insertions, deletions, substitutions, and
+/// transpositions of adjacent characters needed to turn `a` into `b`.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Returns the known name which is the closest to `name`, if there is one
+/// which is close enough to be considered as a typo.
+fn closest(name: &str) -> Option<&'static str> {
+    let limit = (name.chars().count() / 3).max(1);
+
+    NAMES
+        .iter()
+        .map(|known| (distance(name, known), *known))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Merges all the errors of `results` into a single one, if there are any.
+pub fn combined(
+    results: impl IntoIterator<Item = syn::Result<()>>,
+) -> syn::Result<()> {
+    let mut errors = results.into_iter().filter_map(Result::err);
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|other| error.combine(other));
+            Err(error)
+        },
+        None => Ok(()),
+    }
+}
+
+/// Skips the rest of the current parameter, so that parsing can carry on with
+/// the next one after an error.
+pub fn skip_to_comma(stream: ParseStream) -> syn::Result<()> {
+    while !stream.is_empty() && !stream.peek(Comma) {
+        TokenTree::parse(stream)?;
+    }
+
+    Ok(())
+}
+
+pub fn set_once<T>(
+    slot: &mut Option<Parameter<T>>,
+    parameter: Parameter<T>,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        let message =
+            format!("Named parameter `{}` is already set", parameter.name);
+        return Err(Error::new_spanned(parameter.name, message));
+    }
+
+    *slot = Some(parameter);
+
+    Ok(())
+}
+
+pub fn exclusive<A, B>(
+    parameter: &Option<Parameter<A>>,
+    other: &Option<Parameter<B>>,
+) -> syn::Result<()> {
+    if let (Some(parameter), Some(other)) = (parameter, other) {
+        let message = format!(
+            "Named parameter `{}` cannot be combined with `{}`",
+            other.name,
+            parameter.name,
+        );
+        return Err(Error::new_spanned(&other.name, message));
+    }
+
+    Ok(())
+}
+
+impl<D> Parameters<D>
+where
+    D: Parse,
+{
+    fn parse_parameter(&mut self, stream: ParseStream) -> syn::Result<()> {
+        // NOTE: `as` is a keyword, hence the `parse_any`
+        let identifier = Ident::parse_any(stream).map_err(|error| {
+            let message = format!(
+                "Expected named parameter {}, or \
+                 the end of the macro invocation",
+                listed(&NAMES),
+            );
+            Error::new(error.span(), message)
+        })?;
+
+        match identifier.to_string().as_str() {
+            "join_with" => {
+                set_once(&mut self.join_with, parse_value(stream, identifier)?)
+            },
+            "lang" => {
+                set_once(&mut self.lang, parse_value(stream, identifier)?)
+            },
+            "decode" => {
+                set_once(&mut self.decode, parse_value(stream, identifier)?)
+            },
+            "encode" => {
+                set_once(&mut self.encode, parse_value(stream, identifier)?)
+            },
+            "as" => {
+                set_once(&mut self.output, parse_value(stream, identifier)?)
+            },
+            "allow" => {
+                let value = Allowed::parse(stream)?;
+                set_once(&mut self.allow, Parameter { name: identifier, value })
+            },
+            "allow_invisible" => set_once(
+                &mut self.allow_invisible,
+                parse_flag(stream, identifier)?,
+            ),
+            "max_len" => set_once(
+                &mut self.constraints.max_len,
+                parse_value(stream, identifier)?,
+            ),
+            "min_lines" => set_once(
+                &mut self.constraints.min_lines,
+                parse_value(stream, identifier)?,
+            ),
+            "ascii_only" => set_once(
+                &mut self.constraints.ascii_only,
+                parse_flag(stream, identifier)?,
+            ),
+            "forbid" => set_once(
+                &mut self.constraints.forbid,
+                parse_value(stream, identifier)?,
+            ),
+            "require_utf8" => set_once(
+                &mut self.constraints.require_utf8,
+                parse_flag(stream, identifier)?,
+            ),
+            "debug" => {
+                set_once(&mut self.debug, parse_flag(stream, identifier)?)
+            },
+            "vars" => {
+                set_once(&mut self.vars, parse_value(stream, identifier)?)
+            },
+            "cfg_lines" => {
+                set_once(&mut self.cfg_lines, parse_flag(stream, identifier)?)
+            },
+            name => {
+                let message = match closest(name) {
+                    Some(known) => format!(
+                        "Unknown named parameter `{name}`, did you mean \
+                         `{known}`?",
+                    ),
+                    None => {
+                        format!("Expected named parameter {}", listed(&NAMES))
+                    },
+                };
+                Err(Error::new_spanned(identifier, message))
+            },
+        }
+    }
+
+    /// Parses the named parameters, and reports the errors of `check` (i.e.
+    /// the ones of the combinations of the parameters, which depend on the
+    /// kind of the literal) together with the errors of parsing.
+    pub fn parse_checked(
+        stream: ParseStream,
+        check: impl FnOnce(&Self) -> syn::Result<()>,
+    ) -> syn::Result<Self> {
+        let mut parameters = Self {
+            join_with: None,
+            lang: None,
+            decode: None,
+            encode: None,
+            output: None,
+            allow: None,
+            allow_invisible: None,
+            constraints: Constraints::default(),
+            debug: None,
+            vars: None,
+            cfg_lines: None,
+        };
+
+        let mut results = Vec::new();
+
+        loop {
+            if stream.is_empty() {
+                break;
+            }
+
+            if let Err(error) = Comma::parse(stream) {
+                let message =
+                    "Expected `,` to follow or the end of the macro invocation";
+                results.push(Err(Error::new(error.span(), message)));
+                skip_to_comma(stream)?;
+                continue;
+            }
+
+            if stream.is_empty() {
+                break;
+            }
+
+            let result = parameters.parse_parameter(stream);
+            if result.is_err() {
+                skip_to_comma(stream)?;
+            }
+            results.push(result);
+        }
+
+        results.push(check(&parameters));
+        combined(results)?;
+
+        Ok(parameters)
+    }
+}
+
+impl<D> Parse for Parameters<D>
+where
+    D: Parse,
+{
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        Self::parse_checked(stream, |_| Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        assert_eq!(distance("join_with", "join_with"), 0);
+        assert_eq!(distance("join_wiht", "join_with"), 1);
+        assert_eq!(distance("jion_wth", "join_with"), 2);
+        assert_eq!(distance("", "as"), 2);
+        assert_eq!(distance("lang", "decode"), 6);
+    }
+
+    #[test]
+    fn closest_names() {
+        assert_eq!(closest("join_wiht"), Some("join_with"));
+        assert_eq!(closest("joinwith"), Some("join_with"));
+        assert_eq!(closest("lnag"), Some("lang"));
+        assert_eq!(closest("ecnode"), Some("encode"));
+        assert_eq!(closest("at"), Some("as"));
+        assert_eq!(closest("hello"), None);
+        assert_eq!(closest("x"), None);
+    }
+}
@@ -0,0 +1,176 @@
+use std::ops::Range;
+use proc_macro2::{Span, Literal};
+
+/// Maps the byte ranges of the (unescaped) value of a string, a byte-string,
+/// or a C-string literal back to the spans of the corresponding parts of the
+/// literal's token, so that errors can point inside the literal.
+pub struct Spans {
+    literal: Literal,
+    /// The range of the source of each byte of the value in the token
+    ranges: Vec<Range<usize>>,
+    /// The offset of the closing quote in the token
+    end: usize,
+}
+
+impl Spans {
+    pub fn new(literal: Literal) -> Self {
+        let (ranges, end) = sources_of(&literal.to_string());
+        Self { literal, ranges, end }
+    }
+
+    pub fn span(&self) -> Span {
+        self.literal.span()
+    }
+
+    /// Returns the span of the part of the literal's token which is the source
+    /// of `range` of the literal's value.
+    ///
+    /// `Literal::subspan` is only available on nightly, therefore this falls
+    /// back to the span of the whole literal on stable.
+    pub fn subspan(&self, range: Range<usize>) -> Span {
+        // NOTE: An empty `range` points at the source of the byte it precedes
+        let last = if range.is_empty() { range.start } else { range.end - 1 };
+        let start = self
+            .ranges
+            .get(range.start)
+            .map_or(self.end, |source| source.start);
+        let end = self.ranges.get(last).map_or(self.end, |source| source.end);
+
+        self.literal.subspan(start..end).unwrap_or_else(|| self.span())
+    }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Returns the range of the source of each byte of the value of the literal
+/// represented by `token`, and the offset of its closing quote.
+fn sources_of(token: &str) -> (Vec<Range<usize>>, usize) {
+    let bytes = token.as_bytes();
+    let mut ranges = Vec::with_capacity(bytes.len());
+
+    let mut index = bytes
+        .iter()
+        .position(|&byte| byte == b'"' || byte == b'#')
+        .unwrap_or(bytes.len());
+    let is_raw = bytes[..index].contains(&b'r');
+
+    if is_raw {
+        let hashes = bytes[index..]
+            .iter()
+            .take_while(|&&byte| byte == b'#')
+            .count();
+        let start = index + hashes + 1;
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let end = token[start..]
+            .find(&closing)
+            .map_or(bytes.len(), |end| start + end);
+
+        for (offset, character) in token[start..end].char_indices() {
+            let source = start + offset..start + offset + character.len_utf8();
+            ranges.extend((0..character.len_utf8()).map(|_| source.clone()));
+        }
+
+        return (ranges, end);
+    }
+
+    // NOTE: Skip the opening quote
+    index += 1;
+
+    while let Some(&byte) = bytes.get(index) {
+        let start = index;
+        let length = match byte {
+            b'"' => break,
+            b'\\' => match bytes.get(index + 1) {
+                Some(b'\n' | b'\r') => {
+                    index += 1;
+                    while bytes.get(index).copied().is_some_and(is_whitespace) {
+                        index += 1;
+                    }
+                    continue;
+                },
+                Some(b'x') => {
+                    index += 4;
+                    1
+                },
+                Some(b'u') => {
+                    let end = token[index..]
+                        .find('}')
+                        .map_or(bytes.len(), |end| index + end + 1);
+                    let digits = token[index + 3..end - 1].replace('_', "");
+                    let length = u32::from_str_radix(&digits, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .map_or(1, char::len_utf8);
+                    index = end;
+                    length
+                },
+                _ => {
+                    index += 2;
+                    1
+                },
+            },
+            _ => {
+                let length = token[index..]
+                    .chars()
+                    .next()
+                    .map_or(1, char::len_utf8);
+                index += length;
+                length
+            },
+        };
+
+        let source = start..index.min(bytes.len());
+        ranges.extend((0..length).map(|_| source.clone()));
+    }
+
+    (ranges, index.min(bytes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(token: &str) -> Vec<&str> {
+        let (ranges, _) = sources_of(token);
+        ranges.into_iter().map(|range| &token[range]).collect()
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(sources(r#""ab""#), ["a", "b"]);
+        assert_eq!(sources(r#"b"ab""#), ["a", "b"]);
+        assert_eq!(sources(r#"c"ab""#), ["a", "b"]);
+        assert_eq!(sources("\"\u{f6}\""), ["\u{f6}", "\u{f6}"]);
+        assert_eq!(sources_of(r#""ab""#).1, 3);
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            sources(r#""\n\"\x41\u{f6}""#),
+            [r"\n", r#"\""#, r"\x41", r"\u{f6}", r"\u{f6}"],
+        );
+        assert_eq!(sources(r#"b"\xff\0""#), [r"\xff", r"\0"]);
+    }
+
+    #[test]
+    fn line_continuations() {
+        assert_eq!(sources("\"a\\\n    b\""), ["a", "b"]);
+        assert_eq!(sources_of("\"a\\\n    b\"").1, 9);
+    }
+
+    #[test]
+    fn raw() {
+        assert_eq!(sources(r#"r"a\n""#), ["a", "\\", "n"]);
+        assert_eq!(sources(r###"br##"a"#"##"###), ["a", "\"", "#"]);
+        assert_eq!(sources_of(r###"r##"a"##"###).1, 5);
+    }
+
+    #[test]
+    fn suffixed() {
+        assert_eq!(sources(r#""ab"suffix"#), ["a", "b"]);
+        assert_eq!(sources_of(r#""ab"suffix"#).1, 3);
+    }
+}
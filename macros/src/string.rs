@@ -0,0 +1,71 @@
+use std::path;
+use syn::{
+    Error,
+    Ident,
+    Lit::{
+        self,
+        Str,
+        Char,
+    },
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use strim_core::transform;
+
+#[derive(Clone)]
+pub enum Delimiter {
+    Character(char),
+    String(String),
+    /// The path separator of the target platform, i.e. the same as the value
+    /// of `std::path::MAIN_SEPARATOR` would be at run-time
+    MainSeparator,
+}
+
+impl Delimiter {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Character(character) => character.to_string().into_bytes(),
+            Self::String(string) => string.clone().into_bytes(),
+            Self::MainSeparator => path::MAIN_SEPARATOR_STR.into(),
+        }
+    }
+
+    pub fn to_delimiter(&self) -> transform::Delimiter {
+        match self {
+            Self::MainSeparator => transform::Delimiter::MainSeparator,
+            delimiter => transform::Delimiter::Bytes(delimiter.to_bytes()),
+        }
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
+impl Parse for Delimiter {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either a string or a character literal, or \
+             `MAIN_SEPARATOR` as a delimiter";
+
+        if stream.peek(Ident) {
+            let identifier = Ident::parse(stream)?;
+            if identifier != "MAIN_SEPARATOR" {
+                return Err(Error::new_spanned(identifier, MESSAGE));
+            }
+
+            return Ok(Self::MainSeparator);
+        }
+
+        match Lit::parse(stream) {
+            Ok(Char(literal)) => Ok(Self::Character(literal.value())),
+            Ok(Str(literal)) => Ok(Self::String(literal.value())),
+            Ok(any) => Err(Error::new_spanned(any, MESSAGE)),
+            Err(error) => Err(Error::new(error.span(), MESSAGE)),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use syn::{
+    Abi,
+    Attribute,
+    Error,
+    Expr,
+    ExprLit,
+    Ident,
+    Lit,
+    token::Comma,
+    visit_mut::{self, VisitMut},
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use strim_core::{
+    invisible,
+    transform::Output,
+    trimmed_string_joined_with_delimiter,
+    trimmed_byte_string_joined_with_delimiter,
+    trimmed_c_string_joined_with_delimiter,
+};
+use super::{
+    string::Delimiter,
+    spans::Spans,
+    invalid::IntoError,
+    output,
+    parameters::{
+        Flag,
+        Parameter,
+        combined,
+        enabled,
+        listed,
+        parse_flag,
+        parse_value,
+        set_once,
+        skip_to_comma,
+    },
+};
+
+const NAMES: [&str; 3] = ["join_with", "only", "allow_invisible"];
+
+/// Restricts which literals are trimmed by `trim_all`.
+#[derive(Clone, Copy)]
+pub enum Only {
+    /// Raw literals with at least one `#`, e.g. `r#"..."#`
+    Hashed,
+    /// Literals with more than one line
+    Multiline,
+}
+
+impl Parse for Only {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either `hashed` or `multiline` as a filter";
+
+        let identifier = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+
+        match identifier.to_string().as_str() {
+            "hashed" => Ok(Self::Hashed),
+            "multiline" => Ok(Self::Multiline),
+            _ => Err(Error::new_spanned(identifier, MESSAGE)),
+        }
+    }
+}
+
+/// The named parameters of `trim_all`.
+pub struct Options {
+    join_with: Option<Parameter<Delimiter>>,
+    only: Option<Parameter<Only>>,
+    allow_invisible: Option<Parameter<Flag>>,
+}
+
+impl Options {
+    fn parse_parameter(&mut self, stream: ParseStream) -> syn::Result<()> {
+        let identifier = Ident::parse(stream).map_err(|error| {
+            let message =
+                format!("Expected named parameter {}", listed(&NAMES));
+            Error::new(error.span(), message)
+        })?;
+
+        match identifier.to_string().as_str() {
+            "join_with" => {
+                let join_with = parse_value(stream, identifier)?;
+                if let Delimiter::MainSeparator = join_with.value {
+                    let message =
+                        "`MAIN_SEPARATOR` cannot be used with `trim_all`, as \
+                         it does not produce a literal";
+                    return Err(Error::new_spanned(join_with.name, message));
+                }
+                set_once(&mut self.join_with, join_with)
+            },
+            "only" => {
+                set_once(&mut self.only, parse_value(stream, identifier)?)
+            },
+            "allow_invisible" => set_once(
+                &mut self.allow_invisible,
+                parse_flag(stream, identifier)?,
+            ),
+            _ => {
+                let message =
+                    format!("Expected named parameter {}", listed(&NAMES));
+                Err(Error::new_spanned(identifier, message))
+            },
+        }
+    }
+
+    /// Checks whether the delimiter contains invisible characters (unless
+    /// they are allowed).
+    fn check_delimiter(&self) -> syn::Result<()> {
+        let Some(join_with) = &self.join_with else {
+            return Ok(());
+        };
+        if enabled(&self.allow_invisible).is_some() {
+            return Ok(());
+        }
+
+        invisible::check(&join_with.value.to_bytes()).map_err(|invalid| {
+            let message = format!("Invalid delimiter: {}", invalid.message);
+            Error::new_spanned(&join_with.name, message)
+        })
+    }
+}
+
+impl Parse for Options {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        let mut options = Self {
+            join_with: None,
+            only: None,
+            allow_invisible: None,
+        };
+
+        let mut results = Vec::new();
+
+        while !stream.is_empty() {
+            let result = options.parse_parameter(stream);
+            if result.is_err() {
+                skip_to_comma(stream)?;
+            }
+            results.push(result);
+
+            if stream.is_empty() {
+                break;
+            }
+
+            if let Err(error) = Comma::parse(stream) {
+                let message = "Expected `,` to follow or the end of the \
+                               attribute's arguments";
+                results.push(Err(Error::new(error.span(), message)));
+                skip_to_comma(stream)?;
+                Comma::parse(stream).ok();
+            }
+        }
+        results.push(options.check_delimiter());
+        combined(results)?;
+
+        Ok(options)
+    }
+}
+
+/// Trims the literals of the expressions in an item, and collects the errors.
+pub struct Trimmer {
+    options: Options,
+    delimiter: Vec<u8>,
+    errors: Vec<Error>,
+}
+
+impl Trimmer {
+    pub fn new(options: Options) -> Self {
+        let delimiter = options
+            .join_with
+            .as_ref()
+            .map(|join_with| join_with.value.to_bytes())
+            .unwrap_or_default();
+
+        Self { options, delimiter, errors: Vec::new() }
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        combined(self.errors.into_iter().map(Err))
+    }
+
+    /// Returns whether the literal represented by `token` and having `value`
+    /// should be trimmed.
+    fn is_selected(&self, token: &str, value: &[u8]) -> bool {
+        let Some(only) = &self.options.only else {
+            return true;
+        };
+
+        match only.value {
+            Only::Hashed => {
+                token.trim_start_matches(['b', 'c']).starts_with("r#")
+            },
+            Only::Multiline => value.contains(&b'\n'),
+        }
+    }
+
+    fn trimmed(&self, literal: &Lit) -> Option<syn::Result<Expr>> {
+        let (value, token, output) = match literal {
+            Lit::Str(literal) if literal.suffix().is_empty() => {
+                (literal.value().into_bytes(), literal.token(), Output::String)
+            },
+            Lit::ByteStr(literal) if literal.suffix().is_empty() => {
+                (literal.value(), literal.token(), Output::ByteString)
+            },
+            Lit::CStr(literal) if literal.suffix().is_empty() => {
+                (literal.value().into_bytes(), literal.token(), Output::CString)
+            },
+            _ => return None,
+        };
+
+        if !self.is_selected(&token.to_string(), &value) {
+            return None;
+        }
+
+        let spans = Spans::new(token);
+        if enabled(&self.options.allow_invisible).is_none() {
+            if let Err(invalid) = invisible::check(&value) {
+                return Some(Err(invalid.into_error(&value, &spans)));
+            }
+        }
+
+        let bytes = match output {
+            Output::String => {
+                // NOTE: Both the literal and the delimiter are valid UTF-8
+                let value = String::from_utf8_lossy(&value);
+                let delimiter = String::from_utf8_lossy(&self.delimiter);
+                trimmed_string_joined_with_delimiter(&value, &delimiter)
+                    .into_bytes()
+            },
+            Output::CString => {
+                trimmed_c_string_joined_with_delimiter(&value, &self.delimiter)
+            },
+            _ => {
+                trimmed_byte_string_joined_with_delimiter(
+                    &value,
+                    &self.delimiter,
+                )
+            },
+        };
+
+        let tokens = match output::emit(output, bytes) {
+            Ok(tokens) => TokenStream::from(tokens),
+            Err(invalid) => {
+                let span = self
+                    .options
+                    .join_with
+                    .as_ref()
+                    .map_or(spans.span(), |join_with| join_with.name.span());
+                let message =
+                    format!("Invalid delimiter: {}", invalid.message);
+                return Some(Err(Error::new(span, message)));
+            },
+        };
+        Some(Ok(Expr::Verbatim(respanned(tokens, spans.span()))))
+    }
+}
+
+/// Sets the span of the literals among `tokens` to `span`, so that the errors
+/// about the produced expression point at the original literal.
+fn respanned(
+    tokens: TokenStream,
+    span: Span,
+) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Literal(literal) = &mut token {
+                literal.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+impl VisitMut for Trimmer {
+    fn visit_expr_mut(&mut self, expression: &mut Expr) {
+        if let Expr::Lit(ExprLit { attrs, lit }) = expression {
+            if attrs.is_empty() {
+                match self.trimmed(lit) {
+                    Some(Ok(trimmed)) => *expression = trimmed,
+                    Some(Err(error)) => self.errors.push(error),
+                    None => (),
+                }
+                return;
+            }
+        }
+
+        visit_mut::visit_expr_mut(self, expression);
+    }
+
+    // NOTE: Doc comments and the arguments of attributes are left alone
+    fn visit_attribute_mut(&mut self, _: &mut Attribute) {}
+
+    // NOTE: The ABI of `extern "C"` is a string literal as well
+    fn visit_abi_mut(&mut self, _: &mut Abi) {}
+}
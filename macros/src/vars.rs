@@ -0,0 +1,140 @@
+use std::env;
+use syn::{
+    Error,
+    Ident,
+    LitStr,
+    braced,
+    parenthesized,
+    token::{Eq, Comma},
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+use proc_macro::{
+    Group,
+    Literal,
+    Delimiter,
+    TokenTree,
+    TokenStream,
+};
+
+/// A variable of `vars = { ... }`, whose value is either a string literal, or
+/// the value of an environment variable, e.g. `env("CARGO_PKG_VERSION")`.
+#[derive(Clone)]
+struct Variable {
+    name: Ident,
+    value: String,
+    /// The name of the environment variable the value is read from
+    env: Option<String>,
+}
+
+impl Parse for Variable {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str =
+            "Expected either a string literal or `env(\"...\")` as a value";
+
+        let name = Ident::parse(stream).map_err(|error| {
+            Error::new(error.span(), "Expected the name of a variable")
+        })?;
+        Eq::parse(stream).map_err(|error| {
+            let message = format!("Expected `{name}` to be followed by a `=`");
+            Error::new(error.span(), message)
+        })?;
+
+        if stream.peek(LitStr) {
+            let value = stream.parse::<LitStr>()?.value();
+            return Ok(Self { name, value, env: None });
+        }
+
+        let function = Ident::parse(stream)
+            .map_err(|error| Error::new(error.span(), MESSAGE))?;
+        if function != "env" {
+            return Err(Error::new_spanned(function, MESSAGE));
+        }
+
+        let content;
+        parenthesized!(content in stream);
+        let variable = content.parse::<LitStr>()?;
+        let value = env::var(variable.value()).map_err(|error| {
+            let message = format!(
+                "Cannot read environment variable `{}`: {error}",
+                variable.value(),
+            );
+            Error::new_spanned(&variable, message)
+        })?;
+
+        Ok(Self { name, value, env: Some(variable.value()) })
+    }
+}
+
+/// The variables whose `{name}` placeholders are substituted in the trimmed
+/// string.
+#[derive(Clone)]
+pub struct Vars {
+    variables: Vec<Variable>,
+}
+
+impl Parse for Vars {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in stream);
+
+        let mut variables = Vec::<Variable>::new();
+        while !content.is_empty() {
+            let variable = Variable::parse(&content)?;
+            if variables.iter().any(|other| other.name == variable.name) {
+                let message =
+                    format!("Variable `{}` is already set", variable.name);
+                return Err(Error::new_spanned(variable.name, message));
+            }
+            variables.push(variable);
+
+            if !content.is_empty() {
+                Comma::parse(&content)?;
+            }
+        }
+
+        Ok(Self { variables })
+    }
+}
+
+impl Vars {
+    /// Returns the names and the values of the variables, whose `{name}`
+    /// placeholders are substituted by the transformations.
+    pub fn to_vars(&self) -> Vec<(String, String)> {
+        self.variables
+            .iter()
+            .map(|variable| {
+                (variable.name.to_string(), variable.value.clone())
+            })
+            .collect()
+    }
+
+    /// Wraps `expression` in a block which reads the environment variables
+    /// with `option_env!`, so that the compiler tracks them, and the crate is
+    /// rebuilt whenever any of them changes.
+    pub fn tracked(&self, expression: TokenStream) -> TokenStream {
+        let mut block = TokenStream::new();
+        for variable in &self.variables {
+            let Some(env) = &variable.env else {
+                continue;
+            };
+
+            let item = format!(
+                "const _: ::core::option::Option<&str> = \
+                 ::core::option_env!({});",
+                Literal::string(env),
+            );
+            block.extend(item.parse::<TokenStream>());
+        }
+
+        if block.is_empty() {
+            return expression;
+        }
+
+        block.extend(expression);
+
+        TokenTree::from(Group::new(Delimiter::Brace, block)).into()
+    }
+}
@@ -0,0 +1,249 @@
+mod source;
+mod migrate;
+mod revert;
+
+use std::{env, fs, process::ExitCode};
+use syn::spanned::Spanned;
+use self::source::Source;
+
+const USAGE: &str = "\
+Usage: strim-migrate [OPTIONS] FILE...
+
+Rewrites the string literals which use line continuations (i.e. `\\` at the end
+of a line) in the given Rust source files to equivalent `strim::trim!`
+invocations of raw string literals.  Each rewrite is verified with the same
+trimming logic `trim!` uses, and the literals which cannot be rewritten are
+reported and left as they are.
+
+Options:
+    --reverse    Rewrite the `trim!` invocations which have no named parameters
+                 other than `join_with` to literals using line continuations,
+                 and remove `use strim::trim;` if it is no longer needed
+    --dry-run    Report the rewrites without changing any of the files
+    -h, --help   Print this help and exit
+    -V, --version
+                 Print the version and exit";
+
+struct Options {
+    reverse: bool,
+    dry_run: bool,
+    files: Vec<String>,
+}
+
+enum Command {
+    Help,
+    Version,
+    Migrate(Options),
+}
+
+/// Parses the command-line `arguments` (without the name of the program).
+fn parse(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Command, String> {
+    let mut options = Options {
+        reverse: false,
+        dry_run: false,
+        files: Vec::new(),
+    };
+    let mut only_files = false;
+
+    for argument in arguments {
+        if only_files || !argument.starts_with('-') {
+            options.files.push(argument);
+            continue;
+        }
+
+        match argument.as_str() {
+            "--" => only_files = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--reverse" => options.reverse = true,
+            "--dry-run" => options.dry_run = true,
+            _ => return Err(format!("Unknown option `{argument}`")),
+        }
+    }
+
+    if options.files.is_empty() {
+        return Err(String::from("Expected at least one file"));
+    }
+
+    Ok(Command::Migrate(options))
+}
+
+/// Rewrites the literals of `source`, and reports the result of each rewrite
+/// as the location of the literal and the reason of the failure, if any.
+fn rewritten(
+    source: &mut Source,
+    file: &syn::File,
+    reverse: bool,
+) -> Vec<(proc_macro2::Span, Option<String>)> {
+    let mut reports = Vec::new();
+
+    if !reverse {
+        for literal in migrate::literals_of(file) {
+            let indentation = source.indentation_at(literal.span());
+            match migrate::migrated(&literal, indentation) {
+                Ok(Some(migrated)) => {
+                    source.replace(literal.span(), migrated);
+                    reports.push((literal.span(), None));
+                },
+                Ok(None) => (),
+                Err(reason) => {
+                    reports.push((literal.span(), Some(reason.to_string())));
+                },
+            }
+        }
+
+        return reports;
+    }
+
+    let invocations = revert::invocations_of(file);
+    for invocation in &invocations.invocations {
+        let span = invocation.span();
+        let indentation = source.indentation_at(span);
+        match revert::reverted(invocation, indentation) {
+            Ok(reverted) => {
+                source.replace(span, reverted);
+                reports.push((span, None));
+            },
+            Err(reason) => reports.push((span, Some(reason))),
+        }
+    }
+
+    // NOTE: Only imports which are certainly unused can be removed
+    let tokens = source.text().parse().unwrap_or_default();
+    if reports.iter().all(|(_, reason)| reason.is_none())
+        && invocations.are_imports_unused(tokens)
+    {
+        for import in &invocations.imports {
+            source.remove(import.span());
+        }
+    }
+
+    reports
+}
+
+fn migrate(path: &str, options: &Options) -> Result<(), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("Cannot read `{path}`: {error}"))?;
+    let mut source = Source::new(text);
+    let file = syn::parse_file(source.text())
+        .map_err(|error| format!("Cannot parse `{path}`: {error}"))?;
+
+    for (span, reason) in rewritten(&mut source, &file, options.reverse) {
+        let location = span.start();
+        let report = match reason {
+            Some(reason) => format!("skipped, because {reason}"),
+            None => String::from("rewritten"),
+        };
+        eprintln!(
+            "{path}:{}:{}: {report}",
+            location.line,
+            location.column + 1,
+        );
+    }
+
+    if options.dry_run || !source.is_edited() {
+        return Ok(());
+    }
+
+    fs::write(path, source.edited())
+        .map_err(|error| format!("Cannot write `{path}`: {error}"))
+}
+
+fn main() -> ExitCode {
+    let options = match parse(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Ok(Command::Version) => {
+            println!("strim-migrate {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        },
+        Ok(Command::Migrate(options)) => options,
+        Err(message) => {
+            eprintln!("strim-migrate: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    for path in &options.files {
+        if let Err(message) = migrate(path, &options) {
+            eprintln!("strim-migrate: {message}");
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(text: &str, reverse: bool) -> String {
+        let mut source = Source::new(String::from(text));
+        let file = syn::parse_file(text).unwrap();
+        rewritten(&mut source, &file, reverse);
+
+        source.edited()
+    }
+
+    const CONTINUED: &str = r#"use std::fmt;
+
+#[doc = "a\
+         b"]
+fn greeting(name: &str) -> &'static str {
+    match name {
+        "x\
+         y" => "",
+        _ => "Hello, \
+            W\u{f6}rld!",
+    }
+}
+"#;
+
+    const TRIMMED: &str = r#"use std::fmt;
+
+#[doc = "a\
+         b"]
+fn greeting(name: &str) -> &'static str {
+    match name {
+        "x\
+         y" => "",
+        _ => strim::trim!(r"
+            Hello,
+            Wörld!
+        ", join_with = ' '),
+    }
+}
+"#;
+
+    #[test]
+    fn migrated() {
+        assert_eq!(rewrite(CONTINUED, false), TRIMMED);
+    }
+
+    #[test]
+    fn reverted() {
+        let trimmed = format!("use strim::trim;\n{TRIMMED}");
+        let continued = CONTINUED.replace("W\\u{f6}", "W\u{f6}");
+        assert_eq!(rewrite(&trimmed, true), continued);
+
+        let nested = format!(
+            "{trimmed}\nfn f() {{ assert_eq!(trim!(\"a\"), \"a\"); }}\n",
+        );
+        assert!(rewrite(&nested, true).starts_with("use strim::trim;\n"));
+    }
+
+    #[test]
+    fn indented_with_tabs() {
+        let continued = "fn f() -> &'static str {\n\t\"a \\\n\t b\"\n}\n";
+        let trimmed = "fn f() -> &'static str {\n\
+                       \tstrim::trim!(r\"\n\t\ta\n\t\tb\n\t\", \
+                       join_with = ' ')\n}\n";
+        assert_eq!(rewrite(continued, false), trimmed);
+    }
+}
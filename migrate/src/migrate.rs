@@ -0,0 +1,201 @@
+use syn::{
+    File,
+    Lit,
+    LitStr,
+    ExprLit,
+    Pat,
+    Attribute,
+    visit::{self, Visit},
+};
+use strim_core::{invisible, trimmed_string_joined_with_delimiter};
+use super::source::nested;
+
+/// Collects the string literals of a file which can be `trim!` invocations,
+/// i.e. the ones in expressions, but not in patterns or in attributes.
+#[derive(Default)]
+struct Literals {
+    literals: Vec<LitStr>,
+}
+
+impl<'a> Visit<'a> for Literals {
+    fn visit_expr_lit(&mut self, expression: &'a ExprLit) {
+        if let Lit::Str(literal) = &expression.lit {
+            self.literals.push(literal.clone());
+        }
+
+        visit::visit_expr_lit(self, expression);
+    }
+
+    fn visit_pat(&mut self, _: &'a Pat) {}
+
+    fn visit_attribute(&mut self, _: &'a Attribute) {}
+}
+
+pub fn literals_of(file: &File) -> Vec<LitStr> {
+    let mut literals = Literals::default();
+    literals.visit_file(file);
+
+    literals.literals
+}
+
+/// Splits the content of the string literal represented by `token` at its
+/// line continuations (i.e. `\` at the end of a line), or returns `None` if it
+/// is not a (non-raw) string literal.
+fn segments_of(token: &str) -> Option<Vec<&str>> {
+    let content = token.strip_prefix('"')?.strip_suffix('"')?;
+    let bytes = content.as_bytes();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] != b'\\' {
+            index += 1;
+            continue;
+        }
+
+        match bytes.get(index + 1) {
+            Some(b'\n' | b'\r') => {
+                segments.push(&content[start..index]);
+                index += 1;
+                while bytes.get(index).is_some_and(u8::is_ascii_whitespace) {
+                    index += 1;
+                }
+                start = index;
+            },
+            _ => index += 2,
+        }
+    }
+    segments.push(&content[start..]);
+
+    Some(segments)
+}
+
+/// Returns the number of `#`s a raw string literal of `content` needs.
+fn hashes_for(content: &str) -> usize {
+    (0..)
+        .find(|&count| !content.contains(&format!("\"{}", "#".repeat(count))))
+        .unwrap_or_default()
+}
+
+/// Rewrites `literal` to an equivalent `trim!` invocation of a raw string
+/// literal, whose lines are indented relative to `indentation`.  Returns
+/// `Ok(None)` if the literal does not use line continuations at all.
+pub fn migrated(
+    literal: &LitStr,
+    indentation: &str,
+) -> Result<Option<String>, &'static str> {
+    let token = literal.token().to_string();
+    let Some(segments) = segments_of(&token).filter(|segments| {
+        segments.len() > 1
+    }) else {
+        return Ok(None);
+    };
+
+    let value = literal.value();
+    let mut lines = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let segment = syn::parse_str::<LitStr>(&format!("\"{segment}\""))
+            .map_err(|_| "it cannot be split at its line continuations")?
+            .value();
+        lines.push(segment.trim().to_string());
+    }
+
+    if lines.iter().any(|line| line.contains('\r')) {
+        return Err("it contains a carriage return, which a raw string \
+                    literal cannot");
+    }
+    if lines.iter().any(|line| line.ends_with('\\')) {
+        return Err("one of its lines would end with a backslash");
+    }
+    if invisible::check(value.as_bytes()).is_err() {
+        return Err("it contains invisible characters");
+    }
+
+    let nested = nested(indentation);
+    let mut content = String::from("\n");
+    for line in lines.iter().filter(|line| !line.is_empty()) {
+        content.push_str(&format!("{nested}{line}\n"));
+    }
+    content.push_str(indentation);
+
+    let join_with = [("", ""), (" ", ", join_with = ' '")]
+        .into_iter()
+        .find(|(delimiter, _)| {
+            trimmed_string_joined_with_delimiter(&content, delimiter) == value
+        })
+        .map(|(_, join_with)| join_with)
+        .ok_or("its lines are joined with neither nothing nor a single space")?;
+
+    let hashes = "#".repeat(hashes_for(&content));
+
+    Ok(Some(format!(
+        "strim::trim!(r{hashes}\"{content}\"{hashes}{join_with})",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrate(token: &str) -> Result<Option<String>, &'static str> {
+        migrated(&syn::parse_str(token).unwrap(), "    ")
+    }
+
+    #[test]
+    fn segments() {
+        assert_eq!(
+            segments_of("\"a \\\n    b\\\\\\\r\n\t\\\"c\""),
+            Some(vec!["a ", "b\\\\", "\\\"c"]),
+        );
+        assert_eq!(segments_of("r\"a\""), None);
+    }
+
+    #[test]
+    fn not_continued() {
+        assert_eq!(migrate("\"a\\\\\nb\""), Ok(None));
+    }
+
+    #[test]
+    fn joined_with_nothing() {
+        assert_eq!(
+            migrate("\"<a>\\\n        <b>\\\"x\\\"</b>\\\n    </a>\"").unwrap(),
+            Some(String::from(
+                "strim::trim!(r#\"\n        <a>\n        <b>\"x\"</b>\n        \
+                 </a>\n    \"#)",
+            )),
+        );
+    }
+
+    #[test]
+    fn joined_with_space() {
+        assert_eq!(
+            migrate("\"Hello, \\\n        World!\"").unwrap(),
+            Some(String::from(
+                "strim::trim!(r\"\n        Hello,\n        World!\n    \", \
+                 join_with = ' ')",
+            )),
+        );
+    }
+
+    #[test]
+    fn indented_with_tabs() {
+        let literal = syn::parse_str("\"Hello, \\\n\t\t World!\"").unwrap();
+        assert_eq!(
+            migrated(&literal, "\t").unwrap(),
+            Some(String::from(
+                "strim::trim!(r\"\n\t\tHello,\n\t\tWorld!\n\t\", \
+                 join_with = ' ')",
+            )),
+        );
+    }
+
+    #[test]
+    fn not_equivalent() {
+        assert!(migrate("\"a  \\\n    b\"").is_err());
+        assert!(migrate("\"a\\t\\\n    b\"").is_err());
+        assert!(migrate("\"a\\r\\\n    b\"").is_err());
+        assert!(migrate("\"a\\\\\\\n    b\"").is_err());
+        assert!(migrate("\"a\\u{200b}\\\n    b\"").is_err());
+    }
+}
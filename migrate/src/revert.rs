@@ -0,0 +1,277 @@
+use syn::{
+    Error,
+    File,
+    Ident,
+    ItemUse,
+    UseTree,
+    LitStr,
+    ExprMacro,
+    StmtMacro,
+    Macro,
+    Lit::{self, Str, Char},
+    token::{Comma, Eq},
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    visit::Visit,
+};
+use proc_macro2::{TokenTree, TokenStream};
+use strim_core::{string, trimmed_string_joined_with_delimiter};
+use super::source::nested;
+
+/// Collects the `trim!` invocations in the expressions and the statements of a
+/// file, and the `use strim::trim;` items which import the macro.
+#[derive(Default)]
+pub struct Invocations {
+    pub invocations: Vec<Macro>,
+    pub imports: Vec<ItemUse>,
+}
+
+fn is_trim(invocation: &Macro) -> bool {
+    let mut names = invocation
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string());
+
+    match (names.next(), names.next(), names.next()) {
+        (Some(name), None, None) => name == "trim",
+        (Some(krate), Some(name), None) => krate == "strim" && name == "trim",
+        _ => false,
+    }
+}
+
+fn is_trim_import(tree: &UseTree) -> bool {
+    match tree {
+        UseTree::Path(path) if path.ident == "strim" => {
+            matches!(&*path.tree, UseTree::Name(name) if name.ident == "trim")
+        },
+        _ => false,
+    }
+}
+
+/// Returns the number of `trim` identifiers among `tokens`, including the ones
+/// nested in groups.
+fn trim_count(tokens: TokenStream) -> usize {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(identifier) => usize::from(identifier == "trim"),
+            TokenTree::Group(group) => trim_count(group.stream()),
+            _ => 0,
+        })
+        .sum()
+}
+
+impl Invocations {
+    fn visit_macro_of(&mut self, invocation: &Macro) {
+        if is_trim(invocation) {
+            self.invocations.push(invocation.clone());
+        }
+    }
+
+    /// Returns whether the imports would be unused once all the invocations
+    /// are rewritten, i.e. whether `tokens` (the ones of the whole file) have
+    /// no other `trim` identifiers, not even among the tokens of other macros
+    /// (e.g. of `assert_eq!(trim!(...), ...)`), which are not parsed.
+    pub fn are_imports_unused(&self, tokens: TokenStream) -> bool {
+        let invocations: usize = self
+            .invocations
+            .iter()
+            .map(|invocation| 1 + trim_count(invocation.tokens.clone()))
+            .sum();
+
+        trim_count(tokens) == invocations + self.imports.len()
+    }
+}
+
+impl<'a> Visit<'a> for Invocations {
+    fn visit_expr_macro(&mut self, expression: &'a ExprMacro) {
+        self.visit_macro_of(&expression.mac);
+    }
+
+    fn visit_stmt_macro(&mut self, statement: &'a StmtMacro) {
+        self.visit_macro_of(&statement.mac);
+    }
+
+    fn visit_item_use(&mut self, item: &'a ItemUse) {
+        if item.leading_colon.is_none() && is_trim_import(&item.tree) {
+            self.imports.push(item.clone());
+        }
+    }
+}
+
+pub fn invocations_of(file: &File) -> Invocations {
+    let mut invocations = Invocations::default();
+    invocations.visit_file(file);
+
+    invocations
+}
+
+/// The arguments of a `trim!` invocation which can be reverted.
+struct Arguments {
+    literal: LitStr,
+    join_with: String,
+}
+
+impl Parse for Arguments {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        const MESSAGE: &str = "it has named parameters other than `join_with`";
+
+        let literal = stream
+            .parse::<LitStr>()
+            .map_err(|error| Error::new(error.span(), "it is not a string"))?;
+        let mut join_with = String::new();
+
+        if stream.parse::<Option<Comma>>()?.is_some() && !stream.is_empty() {
+            let name = Ident::parse(stream)?;
+            if name != "join_with" {
+                return Err(Error::new_spanned(name, MESSAGE));
+            }
+
+            Eq::parse(stream)?;
+            join_with = match Lit::parse(stream)? {
+                Str(delimiter) => delimiter.value(),
+                Char(delimiter) => delimiter.value().to_string(),
+                any => {
+                    let message = "its delimiter is not a literal";
+                    return Err(Error::new_spanned(any, message));
+                },
+            };
+            stream.parse::<Option<Comma>>()?;
+        }
+
+        if !stream.is_empty() {
+            return Err(Error::new(stream.span(), MESSAGE));
+        }
+
+        Ok(Self { literal, join_with })
+    }
+}
+
+/// Rewrites the `invocation` of `trim!` to an equivalent string literal, which
+/// uses line continuations and whose lines are indented relative to
+/// `indentation`.
+pub fn reverted(
+    invocation: &Macro,
+    indentation: &str,
+) -> Result<String, String> {
+    let Arguments { literal, join_with } = invocation
+        .parse_body()
+        .map_err(|error| error.to_string())?;
+    if !literal.suffix().is_empty() {
+        return Err(String::from("its literal has a suffix"));
+    }
+
+    let input = literal.value();
+    let expected = trimmed_string_joined_with_delimiter(&input, &join_with);
+    let lines: Vec<_> = string::Lines::from(input.as_str())
+        .map(|line| line.escape_debug().to_string())
+        .collect();
+    let separator = format!(
+        "{}\\\n{}",
+        join_with.escape_debug(),
+        nested(indentation),
+    );
+    let reverted = format!("\"{}\"", lines.join(&separator));
+
+    match syn::parse_str::<LitStr>(&reverted) {
+        Ok(literal) if literal.value() == expected => Ok(reverted),
+        _ => Err(String::from("the continued literal would not be equivalent")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revert(source: &str) -> Result<String, String> {
+        let file = syn::parse_file(source).unwrap();
+        let invocations = invocations_of(&file);
+        assert_eq!(invocations.invocations.len(), 1);
+
+        reverted(&invocations.invocations[0], "")
+    }
+
+    #[test]
+    fn found() {
+        let file = syn::parse_file(
+            "use strim::trim;\n\
+             use other::trim;\n\
+             const A: &str = trim!(\"a\");\n\
+             const B: &str = strim::trim!(\"b\");\n\
+             const C: &str = other::trim!(\"c\");",
+        )
+        .unwrap();
+        let invocations = invocations_of(&file);
+
+        assert_eq!(invocations.imports.len(), 1);
+        assert_eq!(invocations.invocations.len(), 2);
+    }
+
+    #[test]
+    fn statements() {
+        let file =
+            syn::parse_file("fn f() -> &str { trim!(\"a\") }").unwrap();
+
+        assert_eq!(invocations_of(&file).invocations.len(), 1);
+    }
+
+    #[test]
+    fn unused_imports() {
+        let is_unused = |source: &str| {
+            let file = syn::parse_file(source).unwrap();
+            invocations_of(&file).are_imports_unused(source.parse().unwrap())
+        };
+
+        assert!(is_unused("use strim::trim;\nconst A: &str = trim!(\"a\");"));
+        assert!(!is_unused(
+            "use strim::trim;\n\
+             const A: &str = trim!(\"a\");\n\
+             fn f() { assert_eq!(trim!(\"b\"), \"b\"); }",
+        ));
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(
+            revert("const A: &str = trim!(\"\n    \\\"a\\\"\t\n\");").unwrap(),
+            "\"\\\"a\\\"\"",
+        );
+    }
+
+    #[test]
+    fn multiple_lines() {
+        assert_eq!(
+            revert("const A: &str = strim::trim!(r\"\n  a\n\n  b\n\");")
+                .unwrap(),
+            "\"a\\\n    b\"",
+        );
+        assert_eq!(
+            revert(
+                "const A: &str = trim!(\"a\n b\n c\", join_with = \", \",);",
+            )
+            .unwrap(),
+            "\"a, \\\n    b, \\\n    c\"",
+        );
+        assert_eq!(
+            revert("const A: &str = trim!(\"a\n\tb\", join_with = '\\n');")
+                .unwrap(),
+            "\"a\\n\\\n    b\"",
+        );
+    }
+
+    #[test]
+    fn not_revertible() {
+        assert_eq!(
+            revert("const A: &[u8] = trim!(b\"a\");").unwrap_err(),
+            "it is not a string",
+        );
+        assert_eq!(
+            revert("const A: &str = trim!(\"a\", lang = graphql);")
+                .unwrap_err(),
+            "it has named parameters other than `join_with`",
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::ops::Range;
+use proc_macro2::{Span, LineColumn};
+
+/// Returns the indentation of the lines nested one level deeper than the ones
+/// indented by `indentation`, i.e. with one more tab if it is indented with
+/// tabs, otherwise with four more spaces.
+pub fn nested(indentation: &str) -> String {
+    match indentation.starts_with('\t') {
+        true => format!("{indentation}\t"),
+        false => format!("{indentation}    "),
+    }
+}
+
+/// The text of a source file, and the edits to be made to it, where the parts
+/// to be edited are referred to by the spans of its syntax tree.
+pub struct Source {
+    text: String,
+    /// The offset of the first byte of each line
+    lines: Vec<usize>,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+impl Source {
+    pub fn new(text: String) -> Self {
+        let lines = [0]
+            .into_iter()
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self { text, lines, edits: Vec::new() }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn offset_of(&self, location: LineColumn) -> usize {
+        let start = self.lines[location.line - 1];
+        self.text[start..]
+            .char_indices()
+            .nth(location.column)
+            .map_or(self.text.len(), |(offset, _)| start + offset)
+    }
+
+    fn range_of(&self, span: Span) -> Range<usize> {
+        self.offset_of(span.start())..self.offset_of(span.end())
+    }
+
+    /// Returns the leading whitespace of the line `span` starts in.
+    pub fn indentation_at(&self, span: Span) -> &str {
+        let start = self.lines[span.start().line - 1];
+        let line = &self.text[start..];
+        let end = line
+            .find(|character: char| !matches!(character, ' ' | '\t'))
+            .unwrap_or(line.len());
+
+        &line[..end]
+    }
+
+    pub fn replace(&mut self, span: Span, replacement: String) {
+        let range = self.range_of(span);
+        self.edits.push((range, replacement));
+    }
+
+    /// Removes the part `span` refers to, and also the whole line it is in if
+    /// nothing else is left there.
+    pub fn remove(&mut self, span: Span) {
+        let Range { mut start, mut end } = self.range_of(span);
+
+        let line_start = self.lines[span.start().line - 1];
+        let before = &self.text[line_start..start];
+        let after = &self.text[end..];
+        let line_end = after.find('\n').map_or(after.len(), |end| end + 1);
+        if before.trim().is_empty() && after[..line_end].trim().is_empty() {
+            start = line_start;
+            end += line_end;
+        }
+
+        self.edits.push((start..end, String::new()));
+    }
+
+    pub fn is_edited(&self) -> bool {
+        !self.edits.is_empty()
+    }
+
+    /// Returns the text with all the edits made to it.
+    pub fn edited(mut self) -> String {
+        // NOTE: Editing from the back keeps the offsets of the rest valid
+        self.edits.sort_by_key(|(range, _)| range.start);
+        for (range, replacement) in self.edits.into_iter().rev() {
+            self.text.replace_range(range, &replacement);
+        }
+
+        self.text
+    }
+}
@@ -9,8 +9,10 @@ function run {
 
     local command="cargo $version test --color $colour"
 
-    bash -c "$command";
+    bash -c "$command --workspace";
     cd dummy && bash -c "$command";
+    bash -c "$command --features c-str-fallback";
+    cd metadata && bash -c "$command";
 }
 
 ARGUMENT="${1-}";
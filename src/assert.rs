@@ -0,0 +1,90 @@
+use strim_core::{diff::diff, transform::Options};
+
+/// [`assert_trimmed_eq!`] asserts that the actual value (anything which is
+/// `AsRef<str>`) is the same as the expected string literal, which is trimmed
+/// at compile time the same way [`trim!`] would trim it, and it accepts the
+/// same named parameters as well, except for `decode`, `encode`, and `as`.
+///
+/// With the named parameter `trim_actual` (or `trim_actual = true`) the actual
+/// value is trimmed too at run-time, with the same `join_with`, `lang`, and
+/// `allow_invisible` as the expected one, e.g. to compare rendered output,
+/// without caring about its indentation.
+///
+/// If the values are not the same, the assertion panics with a line-by-line
+/// diff of them, in which the whitespace is made visible, i.e. a space is shown
+/// as `·`, a tab as `→`, and a carriage return as `␍` (while these characters
+/// themselves are shown as their Unicode escapes).  Large diffs list all the
+/// differing lines of the expected value, then all the ones of the actual
+/// value, instead of interleaving them:
+///
+/// ```text
+/// assertion `actual == expected` failed (after trimming)
+/// --- expected
+/// +++ actual
+///   SELECT·name
+/// - FROM·users
+/// + FROM·users·
+/// ```
+///
+/// # Example
+///
+/// ```
+/// let rendered = format!("SELECT {}\nFROM users", "name");
+/// strim::assert_trimmed_eq!(rendered, r#"
+///     SELECT name
+///     FROM users
+/// "#, join_with = '\n');
+///
+/// let rendered = "
+///     SELECT name
+///         FROM users
+/// ";
+/// strim::assert_trimmed_eq!(rendered, "
+///     SELECT name
+///     FROM users
+/// ", join_with = ' ', trim_actual);
+/// ```
+///
+/// [`trim!`]: crate::trim
+/// [`assert_trimmed_eq!`]: crate::assert_trimmed_eq
+#[macro_export]
+macro_rules! assert_trimmed_eq {
+    ($($arguments:tt)*) => {
+        $crate::__assert_trimmed! { ($crate) $($arguments)* }
+    };
+}
+
+/// Compares the values of `assert_trimmed_eq!` (after trimming `actual` with
+/// `options`, if there are any), which is not meant to be used directly.
+#[doc(hidden)]
+#[track_caller]
+pub fn compare_trimmed(
+    actual: &str,
+    expected: &str,
+    options: Option<&Options>,
+) {
+    let trimmed;
+    let actual = match options {
+        Some(options) => {
+            trimmed = options
+                .trimmed(actual.as_bytes())
+                .unwrap_or_else(|message| {
+                    panic!("Cannot trim the actual value: {message}")
+                });
+            // NOTE: The options are only for strings, hence the output is
+            //       valid UTF-8
+            std::str::from_utf8(&trimmed).expect("Invalid trimmed string")
+        },
+        None => actual,
+    };
+
+    if actual != expected {
+        panic!(
+            "assertion `actual == expected` failed (after trimming)\n\
+             --- expected\n\
+             +++ actual\n\
+             {}",
+            diff(expected, actual),
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Helpers for build scripts, which apply the same transformations to files as
+//! [`trim!`](crate::trim) applies to literals, e.g. to generate trimmed assets
+//! into `OUT_DIR`, which are too large to keep in macro invocations.
+//!
+//! All the helpers print `cargo:rerun-if-changed` for their inputs, so that
+//! the build script is rerun whenever any of them changes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! // build.rs
+//! use std::{env, path::Path};
+//! use strim::build::{Options, Delimiter, trim_file, trim_dir};
+//!
+//! let out_dir = env::var("OUT_DIR").unwrap();
+//! let options = Options {
+//!     join_with: Some(Delimiter::from(' ')),
+//!     ..Options::default()
+//! };
+//!
+//! trim_file(
+//!     "queries/users.sql",
+//!     Path::new(&out_dir).join("users.sql"),
+//!     &options,
+//! ).unwrap();
+//! trim_dir("templates", Path::new(&out_dir).join("templates"), &options)
+//!     .unwrap();
+//! ```
+
+use std::{
+    fs,
+    io,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use strim_core::files::files_in;
+
+pub use strim_core::{
+    base64::Alphabet,
+    constraint::Constraints,
+    transform::{
+        Options,
+        Literal,
+        Lang,
+        Decode,
+        Delimiter,
+        Encode,
+        Output,
+    },
+};
+
+#[derive(Debug)]
+enum Kind {
+    Io(io::Error),
+    Invalid(String),
+}
+
+/// The error of a file which could not be read, trimmed, or written.
+#[derive(Debug)]
+pub struct Error {
+    path: PathBuf,
+    kind: Kind,
+}
+
+impl Error {
+    fn io(path: &Path, error: io::Error) -> Self {
+        Self { path: path.to_path_buf(), kind: Kind::Io(error) }
+    }
+
+    /// The path of the file the error is about.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let path = self.path.display();
+        match &self.kind {
+            Kind::Io(error) => write!(formatter, "`{path}`: {error}"),
+            Kind::Invalid(message) => {
+                write!(formatter, "Cannot trim `{path}`: {message}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            Kind::Io(error) => Some(error),
+            Kind::Invalid(_) => None,
+        }
+    }
+}
+
+fn rerun_if_changed(path: &Path) {
+    println!("cargo:rerun-if-changed={}", path.display());
+}
+
+/// Applies `options` to the content of the `input` file, and writes the result
+/// to the `output` file, creating its parent directories if necessary.
+pub fn trim_file(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &Options,
+) -> Result<(), Error> {
+    let (input, output) = (input.as_ref(), output.as_ref());
+    rerun_if_changed(input);
+
+    let content = fs::read(input).map_err(|error| Error::io(input, error))?;
+    let trimmed = options.trimmed(&content).map_err(|message| Error {
+        path: input.to_path_buf(),
+        kind: Kind::Invalid(message),
+    })?;
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|error| Error::io(parent, error))?;
+    }
+    fs::write(output, trimmed).map_err(|error| Error::io(output, error))
+}
+
+/// Applies `options` to all the files in the `input` directory (including the
+/// ones in its subdirectories), and writes the results to the same relative
+/// paths in the `output` directory.  Returns the paths of the written files.
+pub fn trim_dir(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &Options,
+) -> Result<Vec<PathBuf>, Error> {
+    trim_dir_filtered(input, output, options, |_| true)
+}
+
+/// The same as [`trim_dir`], but it only trims the files for whose path (i.e.
+/// the `input` directory joined with the file's relative path) `filter`
+/// returns `true`, e.g. the ones with a specific extension.
+pub fn trim_dir_filtered(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    options: &Options,
+    filter: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, Error> {
+    let (input, output) = (input.as_ref(), output.as_ref());
+    // NOTE: Cargo scans directories recursively for changes, which covers the
+    //       files that are added later as well
+    rerun_if_changed(input);
+
+    let files = files_in(input, filter)
+        .map_err(|(path, error)| Error::io(&path, error))?;
+
+    let mut written = Vec::with_capacity(files.len());
+    for relative in files {
+        let trimmed = output.join(&relative);
+        trim_file(input.join(relative), &trimmed, options)?;
+        written.push(trimmed);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+    use crate::trim;
+    use super::*;
+
+    fn temporary(name: &str) -> PathBuf {
+        let path = env::temp_dir()
+            .join(format!("strim-build-{}-{name}", process::id()));
+        fs::remove_dir_all(&path).ok();
+        path
+    }
+
+    #[test]
+    fn file() {
+        let directory = temporary("file");
+        let (input, output) =
+            (directory.join("query.sql"), directory.join("out/query.sql"));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&input, "\n    SELECT *\n    FROM users\n").unwrap();
+
+        let options = Options {
+            join_with: Some(Delimiter::from(' ')),
+            ..Options::default()
+        };
+        trim_file(&input, &output, &options).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output).unwrap(),
+            trim!("\n    SELECT *\n    FROM users\n", join_with = ' '),
+        );
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn directory() {
+        let directory = temporary("directory");
+        let (input, output) = (directory.join("in"), directory.join("out"));
+        fs::create_dir_all(input.join("nested")).unwrap();
+        fs::write(input.join("a.txt"), b"  a\n  b  ").unwrap();
+        fs::write(input.join("b.bin"), b"  \xff  ").unwrap();
+        fs::write(input.join("nested/c.txt"), b" c ").unwrap();
+
+        let options = Options {
+            literal: Literal::ByteString,
+            ..Options::default()
+        };
+        let written = trim_dir(&input, &output, &options).unwrap();
+        assert_eq!(
+            written,
+            ["a.txt", "b.bin", "nested/c.txt"].map(|name| output.join(name)),
+        );
+        assert_eq!(
+            fs::read(output.join("a.txt")).unwrap(),
+            trim!(b"  a\n  b  "),
+        );
+        assert_eq!(fs::read(output.join("b.bin")).unwrap(), b"\xff");
+
+        let filtered = directory.join("filtered");
+        let written = trim_dir_filtered(&input, &filtered, &options, |path| {
+            path.extension().is_some_and(|extension| extension == "txt")
+        })
+        .unwrap();
+        assert_eq!(written.len(), 2);
+        assert!(!filtered.join("b.bin").exists());
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_links() {
+        use std::os::unix::fs::symlink;
+
+        let directory = temporary("symbolic_links");
+        let (input, output) = (directory.join("in"), directory.join("out"));
+        let linked = directory.join("linked");
+        fs::create_dir_all(&linked).unwrap();
+        fs::create_dir_all(&input).unwrap();
+        fs::write(linked.join("a.txt"), b" a ").unwrap();
+        symlink(&linked, input.join("linked")).unwrap();
+        symlink(&input, input.join("cycle")).unwrap();
+
+        let written = trim_dir(&input, &output, &Options::default()).unwrap();
+        assert_eq!(written, [output.join("linked/a.txt")]);
+        assert_eq!(fs::read(output.join("linked/a.txt")).unwrap(), b"a");
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn errors() {
+        let directory = temporary("errors");
+        fs::create_dir_all(&directory).unwrap();
+        let input = directory.join("invalid.txt");
+        fs::write(&input, "a\u{200b}").unwrap();
+
+        let options = Options::default();
+        let error =
+            trim_file(&input, directory.join("out"), &options).unwrap_err();
+        assert_eq!(error.path(), input);
+        assert!(error.to_string().ends_with(
+            "Invisible character `U+200B` (zero width space) (at line 1, \
+             column 2)",
+        ));
+        assert!(trim_file(directory.join("missing"), "", &options).is_err());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+/// [`define_trim!`] defines a macro, which is the same as [`trim!`], but with
+/// a preset of named parameters, so that the same conventions can be applied
+/// consistently without repeating them at every invocation.
+///
+/// The first argument is the name of the defined macro, which can be preceded
+/// by attributes (e.g. `#[macro_export]` to make it available to other crates
+/// of a workspace), and it is followed by the named parameters of the preset.
+/// The named parameters which are set at the invocation of the defined macro
+/// override the ones of the preset.  (The parameters of the preset are only
+/// checked when the defined macro is invoked, as their validity depends on the
+/// literal, e.g. `join_with = b' '` can only be used with byte-strings.)
+///
+/// # Example
+///
+/// ```
+/// strim::define_trim!(sql_trim, join_with = ' ', max_len = 128);
+///
+/// let expected = "SELECT name FROM users";
+/// let actual = sql_trim!("
+///     SELECT name
+///     FROM users
+/// ");
+/// assert_eq!(actual, expected);
+///
+/// let expected = "SELECT name\nFROM users";
+/// let actual = sql_trim!("
+///     SELECT name
+///     FROM users
+/// ", join_with = '\n');
+/// assert_eq!(actual, expected);
+/// ```
+///
+/// [`trim!`]: crate::trim
+/// [`define_trim!`]: crate::define_trim
+#[macro_export]
+macro_rules! define_trim {
+    (
+        $(#[$attribute:meta])*
+        $name:ident
+        $(, $($parameters:tt)*)?
+    ) => {
+        $crate::define_trim! {
+            @define ($)
+            $(#[$attribute])*
+            $name
+            { $(, $($parameters)*)? }
+        }
+    };
+    // NOTE: The inner macro cannot refer to its own metavariables with `$`
+    //       directly, hence passing it as a token
+    (
+        @define ($dollar:tt)
+        $(#[$attribute:meta])*
+        $name:ident
+        { $($parameters:tt)* }
+    ) => {
+        $(#[$attribute])*
+        macro_rules! $name {
+            ($dollar($dollar arguments:tt)*) => {
+                $crate::__trim_with_defaults! {
+                    { $($parameters)* }
+                    $dollar($dollar arguments)*
+                }
+            };
+        }
+    };
+}